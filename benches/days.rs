@@ -0,0 +1,41 @@
+use std::fs;
+
+use aoc::{Params, Solution};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Benchmarks parsing and both parts of `S` against the committed input for a day of `year`
+fn bench_day<S: Solution>(c: &mut Criterion, year: u16, day: u8) {
+    let path = format!("inputs/{}/day{:02}.txt", year, day);
+    let input = fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path, err));
+    let parsed = S::parse(&input).unwrap_or_else(|err| panic!("{}: {}", path, err));
+
+    let params = Params::default();
+
+    let mut group = c.benchmark_group(format!("{}/day{:02}", year, day));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part1", |b| {
+        b.iter(|| S::part1(black_box(&parsed), &params))
+    });
+    group.bench_function("part2", |b| {
+        b.iter(|| S::part2(black_box(&parsed), &params))
+    });
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<aoc::year2021::day01::Day01>(c, 2021, 1);
+    bench_day::<aoc::year2021::day02::Day02>(c, 2021, 2);
+    bench_day::<aoc::year2021::day03::Day03>(c, 2021, 3);
+    bench_day::<aoc::year2021::day04::Day04>(c, 2021, 4);
+    bench_day::<aoc::year2021::day05::Day05>(c, 2021, 5);
+    bench_day::<aoc::year2021::day06::Day06>(c, 2021, 6);
+    bench_day::<aoc::year2021::day07::Day07>(c, 2021, 7);
+    bench_day::<aoc::year2021::day08::Day08>(c, 2021, 8);
+    bench_day::<aoc::year2021::day09::Day09>(c, 2021, 9);
+    bench_day::<aoc::year2021::day10::Day10>(c, 2021, 10);
+    bench_day::<aoc::year2021::day11::Day11>(c, 2021, 11);
+    bench_day::<aoc::year2021::day13::Day13>(c, 2021, 13);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
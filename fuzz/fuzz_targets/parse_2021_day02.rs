@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc::year2021::day02::Day02;
+use aoc::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day02::parse(input);
+});
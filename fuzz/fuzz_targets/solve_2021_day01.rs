@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc::options::Options;
+use aoc::solution::solve;
+use aoc::year2021::day01::Day01;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day01>(input, &Options::default());
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc::options::Options;
+use aoc::solution::solve;
+use aoc::year2021::day02::Day02;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day02>(input, &Options::default());
+});
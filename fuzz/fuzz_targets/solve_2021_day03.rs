@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc::options::Options;
+use aoc::solution::solve;
+use aoc::year2021::day03::Day03;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day03>(input, &Options::default());
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc::options::Options;
+use aoc::solution::solve;
+use aoc::year2021::day04::Day04;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day04>(input, &Options::default());
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc::options::Options;
+use aoc::solution::solve;
+use aoc::year2021::day05::Day05;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day05>(input, &Options::default());
+});
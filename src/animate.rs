@@ -0,0 +1,332 @@
+use std::fmt::Write as _;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::visual::{Colour, Frame, Palette};
+
+/// Fastest and slowest the frames can go by
+const MIN_DELAY: Duration = Duration::from_millis(10);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// What the keys pressed while playing do
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    /// Pauses, or carries on playing. Space.
+    Pause,
+    /// Pauses and shows the next frame. `n`.
+    Next,
+    /// Pauses and shows the previous frame. `p`.
+    Previous,
+    /// Halves the time each frame shows for. `+`.
+    Faster,
+    /// Doubles the time each frame shows for. `-`.
+    Slower,
+    /// `q` or Ctrl-C
+    Quit,
+}
+
+impl Control {
+    pub fn from_key(key: u8) -> Option<Control> {
+        match key {
+            b' ' => Some(Control::Pause),
+            b'n' => Some(Control::Next),
+            b'p' => Some(Control::Previous),
+            b'+' | b'=' => Some(Control::Faster),
+            b'-' | b'_' => Some(Control::Slower),
+            // Ctrl-C comes in as a key, as the terminal doesn't send signals while playing
+            b'q' | 3 => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Which frame to show and how fast to move on, as frames go by and keys are pressed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Player {
+    pub frame: usize,
+    pub frames: usize,
+    pub paused: bool,
+    pub delay: Duration,
+}
+
+impl Player {
+    pub fn new(frames: usize, delay: Duration) -> Player {
+        Player {
+            frame: 0,
+            frames,
+            paused: false,
+            delay: delay.clamp(MIN_DELAY, MAX_DELAY),
+        }
+    }
+
+    /// Moves on to the next frame once the delay is over, pausing on the last one
+    pub fn tick(&mut self) {
+        if self.frame + 1 < self.frames {
+            self.frame += 1;
+        }
+        if self.frame + 1 >= self.frames {
+            self.paused = true;
+        }
+    }
+
+    /// Applies a key pressed. Returns `false` when it's time to stop.
+    pub fn control(&mut self, control: Control) -> bool {
+        match control {
+            Control::Pause if self.paused && self.frame + 1 >= self.frames => {
+                // Play again from the start
+                self.frame = 0;
+                self.paused = false;
+            }
+            Control::Pause => self.paused = !self.paused,
+            Control::Next => {
+                self.paused = true;
+                self.frame = (self.frame + 1).min(self.frames.saturating_sub(1));
+            }
+            Control::Previous => {
+                self.paused = true;
+                self.frame = self.frame.saturating_sub(1);
+            }
+            Control::Faster => self.delay = (self.delay / 2).max(MIN_DELAY),
+            Control::Slower => self.delay = (self.delay * 2).min(MAX_DELAY),
+            Control::Quit => return false,
+        }
+
+        true
+    }
+
+    /// Line under the frame with where the animation is at and the keys to control it
+    fn status(&self) -> String {
+        format!(
+            "frame {}/{}  {} ms{}  [space] pause  [n/p] step  [+/-] speed  [q] quit",
+            self.frame + 1,
+            self.frames,
+            self.delay.as_millis(),
+            if self.paused { "  (paused)" } else { "" }
+        )
+    }
+}
+
+/// Draws `frame` with ANSI colours, a character per cell and two rows of cells per line using
+/// half blocks. Only the top left `columns` by `lines` characters are drawn, if given.
+pub fn render(frame: &Frame, palette: &Palette, size: Option<(usize, usize)>) -> String {
+    let (columns, lines) = size.unwrap_or((usize::MAX, usize::MAX));
+    let width = frame.width().min(columns);
+    let height = frame.height().min(lines.saturating_mul(2));
+    let colour = |x: usize, y: usize| {
+        let colours = palette.colours();
+        colours[(frame.row(y)[x] as usize).min(colours.len() - 1)]
+    };
+
+    let mut out = String::new();
+    for y in (0..height).step_by(2) {
+        let mut current: Option<(Colour, Option<Colour>)> = None;
+
+        for x in 0..width {
+            let cell = (colour(x, y), (y + 1 < height).then(|| colour(x, y + 1)));
+            if current != Some(cell) {
+                match cell {
+                    (top, Some(bottom)) => write!(
+                        out,
+                        "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m",
+                        top[0], top[1], top[2], bottom[0], bottom[1], bottom[2]
+                    ),
+                    (top, None) => {
+                        write!(out, "\x1b[0m\x1b[38;2;{};{};{}m", top[0], top[1], top[2])
+                    }
+                }
+                .unwrap();
+                current = Some(cell);
+            }
+            out.push('▀');
+        }
+
+        // Reset the colours and clear whatever the previous frame left on the line
+        out.push_str("\x1b[0m\x1b[K\n");
+    }
+
+    out
+}
+
+/// Plays `frames` in the terminal, redrawing them in place, until the last frame if there is no
+/// way to read keys, or else until `q` is pressed
+pub fn play(frames: &[Frame], palette: &Palette, delay: Duration) -> io::Result<()> {
+    let mut player = Player::new(frames.len(), delay);
+    if frames.is_empty() {
+        return Ok(());
+    }
+
+    let raw_mode = RawMode::enter();
+    let keys = read_keys();
+    let mut keys_open = true;
+    let size = terminal_size().map(|(columns, lines)| (columns, lines.saturating_sub(2)));
+
+    let mut out = io::stdout().lock();
+    // Clear the screen and hide the cursor
+    write!(out, "\x1b[2J\x1b[?25l")?;
+
+    let result = loop {
+        let drawn = write!(
+            out,
+            "\x1b[H{}\x1b[J{}",
+            render(&frames[player.frame], palette, size),
+            player.status()
+        )
+        .and_then(|_| out.flush());
+        if let Err(err) = drawn {
+            break Err(err);
+        }
+
+        if !keys_open {
+            if player.paused {
+                break Ok(());
+            }
+            thread::sleep(player.delay);
+            player.tick();
+            continue;
+        }
+
+        let key = if player.paused {
+            keys.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            keys.recv_timeout(player.delay)
+        };
+
+        match key {
+            Ok(key) => match Control::from_key(key) {
+                Some(control) if !player.control(control) => break Ok(()),
+                _ => {}
+            },
+            Err(RecvTimeoutError::Timeout) => player.tick(),
+            Err(RecvTimeoutError::Disconnected) => keys_open = false,
+        }
+    };
+
+    // Show the cursor again, on a line of its own
+    writeln!(out, "\x1b[?25h")?;
+    out.flush()?;
+    drop(raw_mode);
+
+    result
+}
+
+/// Sends every key pressed, as it is pressed, from a thread of its own
+fn read_keys() -> Receiver<u8> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for key in io::stdin().lock().bytes() {
+            match key {
+                Ok(key) if sender.send(key).is_ok() => {}
+                _ => break,
+            }
+        }
+    });
+
+    receiver
+}
+
+/// Columns and lines of the terminal, if there is one
+fn terminal_size() -> Option<(usize, usize)> {
+    let output = stty(&["size"])?;
+    let (lines, columns) = output.trim().split_once(' ')?;
+    Some((columns.parse().ok()?, lines.parse().ok()?))
+}
+
+/// Runs `stty` on the terminal, returning what it printed if it worked
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        None
+    }
+}
+
+/// Has the terminal send keys as soon as they are pressed, without echoing them, until dropped
+struct RawMode {
+    saved: Option<String>,
+}
+
+impl RawMode {
+    fn enter() -> RawMode {
+        let saved = stty(&["-g"]).map(|settings| settings.trim().to_owned());
+        if saved.is_some() {
+            stty(&["-icanon", "-echo", "-isig", "min", "1"]);
+        }
+        RawMode { saved }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        if let Some(saved) = &self.saved {
+            stty(&[saved]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn it_renders_two_rows_per_line() {
+        let palette = Palette::new(vec![[0, 0, 0], [255, 255, 255]]);
+        let frame = Grid::from_rows(vec![vec![0, 0, 1], vec![1, 1, 1], vec![0, 1, 1]]).unwrap();
+
+        assert_eq!(
+            render(&frame, &palette, None),
+            "\x1b[38;2;0;0;0m\x1b[48;2;255;255;255m▀▀\x1b[38;2;255;255;255m\x1b[48;2;255;255;255m▀\x1b[0m\x1b[K\n\
+             \x1b[0m\x1b[38;2;0;0;0m▀\x1b[0m\x1b[38;2;255;255;255m▀▀\x1b[0m\x1b[K\n"
+        );
+        assert_eq!(
+            render(&frame, &palette, Some((1, 1))),
+            "\x1b[38;2;0;0;0m\x1b[48;2;255;255;255m▀\x1b[0m\x1b[K\n"
+        );
+    }
+
+    #[test]
+    fn it_plays_until_the_last_frame() {
+        let mut player = Player::new(3, Duration::from_millis(100));
+        player.tick();
+        assert_eq!((player.frame, player.paused), (1, false));
+        player.tick();
+        assert_eq!((player.frame, player.paused), (2, true));
+
+        // Unpausing at the end starts over
+        assert!(player.control(Control::Pause));
+        assert_eq!((player.frame, player.paused), (0, false));
+    }
+
+    #[test]
+    fn it_steps_and_changes_speed() {
+        let mut player = Player::new(3, Duration::from_millis(100));
+
+        assert!(player.control(Control::Next));
+        assert_eq!((player.frame, player.paused), (1, true));
+        player.control(Control::Previous);
+        player.control(Control::Previous);
+        assert_eq!(player.frame, 0);
+
+        player.control(Control::Faster);
+        assert_eq!(player.delay, Duration::from_millis(50));
+        for _ in 0..20 {
+            player.control(Control::Slower);
+        }
+        assert_eq!(player.delay, MAX_DELAY);
+
+        assert!(!player.control(Control::Quit));
+        assert_eq!(Control::from_key(b'q'), Some(Control::Quit));
+        assert_eq!(Control::from_key(b'x'), None);
+    }
+}
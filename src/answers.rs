@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::parse::parse_number;
+use crate::{Answer, ParseError};
+
+/// Known answers for the puzzle inputs, as kept in the `answers.txt` manifest.
+///
+/// Every answer goes in its own `<year>/<day>.<part>: <answer>` line, like `2021/05.2: 22116`.
+/// Text answers leave the value empty and continue on the following lines, each one starting
+/// with `|`.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<Key, Answer>,
+}
+
+/// The year, day and part an answer is for
+pub type Key = (u16, u8, u8);
+
+/// Result of checking an answer against the manifest
+#[derive(Debug, PartialEq)]
+pub enum Check<'a> {
+    Correct,
+    Wrong { expected: &'a Answer },
+    Unknown,
+}
+
+impl Answers {
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(year, day, part))
+    }
+
+    /// Checks `answer` against the known answer for a part. Trailing whitespace in text answers
+    /// is not significant.
+    pub fn check(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Check<'_> {
+        match self.get(year, day, part) {
+            Some(expected) if *expected == normalize(answer) => Check::Correct,
+            Some(expected) => Check::Wrong { expected },
+            None => Check::Unknown,
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut answers = BTreeMap::new();
+        // Text answer currently being read, if any
+        let mut text: Option<(Key, Vec<&str>)> = None;
+
+        for (n, line) in input.lines().enumerate() {
+            if let Some(text_line) = line.strip_prefix('|') {
+                match text.as_mut() {
+                    Some((_, lines)) => lines.push(text_line),
+                    None => {
+                        return Err(
+                            ParseError::new(1, "an answer like `2021/05.2: 22116`").offset_lines(n)
+                        )
+                    }
+                }
+                continue;
+            }
+
+            if let Some((key, lines)) = text.take() {
+                answers.insert(key, normalize(&Answer::Text(lines.join("\n"))));
+            }
+
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = parse_entry(line).map_err(|err| err.offset_lines(n))?;
+            if value.is_empty() {
+                text = Some((key, vec![]));
+            } else {
+                let answer = match value.parse::<u64>() {
+                    Ok(number) => Answer::Number(number),
+                    Err(_) => Answer::Text(value.to_owned()),
+                };
+                answers.insert(key, answer);
+            }
+        }
+
+        if let Some((key, lines)) = text {
+            answers.insert(key, normalize(&Answer::Text(lines.join("\n"))));
+        }
+
+        Ok(Answers { answers })
+    }
+}
+
+/// Parses a `<year>/<day>.<part>: <answer>` line into its key and the (trimmed) answer
+fn parse_entry(line: &str) -> Result<(Key, &str), ParseError> {
+    let (key, value) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::new(1, "an answer like `2021/05.2: 22116`"))?;
+
+    Ok((parse_key(line, key)?, value.trim()))
+}
+
+/// Parses the `<year>/<day>.<part>` key of a part found in `line`, like `2021/05.2`
+pub fn parse_key(line: &str, key: &str) -> Result<Key, ParseError> {
+    let expected = "a year, day and part like `2021/05.2`";
+    let (raw_year, day_part) = key
+        .split_once('/')
+        .ok_or_else(|| ParseError::at(line, key, expected))?;
+    let (raw_day, raw_part) = day_part
+        .split_once('.')
+        .ok_or_else(|| ParseError::at(line, key, expected))?;
+
+    let year = parse_number(line, raw_year)?;
+    let day = parse_number(line, raw_day)?;
+    let part = parse_number(line, raw_part)?;
+    if part != 1 && part != 2 {
+        return Err(ParseError::at(line, raw_part, "part 1 or 2"));
+    }
+
+    Ok((year, day, part))
+}
+
+/// Drops trailing whitespace from every line of text answers, so they compare the same no matter
+/// how they were printed or saved
+fn normalize(answer: &Answer) -> Answer {
+    match answer {
+        Answer::Number(n) => Answer::Number(*n),
+        Answer::Text(text) => Answer::Text(
+            text.lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim_end()
+                .to_owned(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MANIFEST: &str = "# Known answers
+2021/01.1: 1553
+2021/01.2: 1597
+
+2021/13.1: 743
+2021/13.2:
+|#  #
+|####
+|#  #
+";
+
+    #[test]
+    fn it_parses_the_manifest() {
+        let answers: Answers = TEST_MANIFEST.parse().unwrap();
+
+        assert_eq!(answers.get(2021, 1, 1), Some(&Answer::Number(1553)));
+        assert_eq!(answers.get(2021, 1, 2), Some(&Answer::Number(1597)));
+        assert_eq!(answers.get(2021, 13, 1), Some(&Answer::Number(743)));
+        assert_eq!(
+            answers.get(2021, 13, 2),
+            Some(&Answer::Text("#  #\n####\n#  #".to_owned()))
+        );
+        assert_eq!(answers.get(2021, 2, 1), None);
+    }
+
+    #[test]
+    fn it_fails_to_parse_bad_entries() {
+        let err = "2021/01.1: 1553\n2021/01.3: 12".parse::<Answers>().err();
+        assert_eq!(err, Some(ParseError::new(9, "part 1 or 2").offset_lines(1)));
+
+        let err = "2021/01.1: 1553\n|####".parse::<Answers>().err();
+        assert_eq!(
+            err,
+            Some(ParseError::new(1, "an answer like `2021/05.2: 22116`").offset_lines(1))
+        );
+
+        // Every answer names its year
+        let err = "05.2: 22116".parse::<Answers>().err();
+        assert_eq!(
+            err,
+            Some(ParseError::new(1, "a year, day and part like `2021/05.2`"))
+        );
+    }
+
+    #[test]
+    fn it_checks_answers() {
+        let answers: Answers = TEST_MANIFEST.parse().unwrap();
+
+        assert_eq!(
+            answers.check(2021, 1, 1, &Answer::Number(1553)),
+            Check::Correct
+        );
+        assert_eq!(
+            answers.check(2021, 1, 2, &Answer::Number(1553)),
+            Check::Wrong {
+                expected: &Answer::Number(1597)
+            }
+        );
+        assert_eq!(
+            answers.check(
+                2021,
+                13,
+                2,
+                &Answer::Text("#  #  \n####\n#  #\n".to_owned())
+            ),
+            Check::Correct
+        );
+        assert_eq!(
+            answers.check(2021, 2, 1, &Answer::Number(1)),
+            Check::Unknown
+        );
+    }
+}
@@ -0,0 +1,8 @@
+use std::process::ExitCode;
+
+use aoc::solution::run;
+use aoc::year2021::day01::Day01;
+
+fn main() -> ExitCode {
+    run::<Day01>()
+}
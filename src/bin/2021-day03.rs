@@ -0,0 +1,8 @@
+use std::process::ExitCode;
+
+use aoc::solution::run;
+use aoc::year2021::day03::Day03;
+
+fn main() -> ExitCode {
+    run::<Day03>()
+}
@@ -0,0 +1,8 @@
+use std::process::ExitCode;
+
+use aoc::solution::run;
+use aoc::year2021::day10::Day10;
+
+fn main() -> ExitCode {
+    run::<Day10>()
+}
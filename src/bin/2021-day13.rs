@@ -0,0 +1,8 @@
+use std::process::ExitCode;
+
+use aoc::solution::run;
+use aoc::year2021::day13::Day13;
+
+fn main() -> ExitCode {
+    run::<Day13>()
+}
@@ -0,0 +1,306 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::config::Config;
+
+const USER_AGENT: &str = "github.com/afgomez/advent-of-code-2021";
+
+/// Minimal client for the Advent of Code website
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// There is no session token to log in with
+    MissingSession,
+    /// The server answered with an error status
+    Status(u16, String),
+    /// The server could not be reached
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session token, set `session` in the config file or AOC_SESSION"
+            ),
+            ClientError::Status(status, body) => {
+                write!(f, "server answered {}: {}", status, body.trim())
+            }
+            ClientError::Transport(err) => write!(f, "{}", err),
+            ClientError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        ClientError::Io(err)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(status, response) => {
+                ClientError::Status(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(transport) => ClientError::Transport(transport.to_string()),
+        }
+    }
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Client, ClientError> {
+        let session = config.session.clone().ok_or(ClientError::MissingSession)?;
+
+        Ok(Client {
+            base_url: config.base_url.trim_end_matches('/').to_owned(),
+            session,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        })
+    }
+
+    /// Downloads the puzzle input of the logged in user
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+
+        Ok(response.into_string()?)
+    }
+
+    /// Submits the answer to a part of a puzzle, and returns the page the website answers with
+    pub fn answer(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, ClientError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        Ok(response.into_string()?)
+    }
+}
+
+/// Where the input of a day came from
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Makes sure the input for a day of `year` is saved at `path`, downloading it only if it's not
+/// there yet. Inputs never change, so a cached file is never downloaded again. An empty file, like
+/// the stub `aoc new` leaves, doesn't count as cached.
+pub fn fetch_input(
+    config: &Config,
+    year: u16,
+    day: u8,
+    path: &Path,
+) -> Result<Fetched, ClientError> {
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = Client::new(config)?.input(year, day)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, input)?;
+
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread::{self, JoinHandle};
+
+    /// Stand-in for the website: answers a single request with `status` and `body`, and hands
+    /// back the raw request it got
+    pub(crate) fn serve_once(status: u16, body: &str) -> (String, JoinHandle<String>) {
+        let (base_url, handle) = serve(&[(status, body)]);
+        (
+            base_url,
+            thread::spawn(move || handle.join().unwrap().remove(0)),
+        )
+    }
+
+    /// Like `serve_once`, answering a request per response in turn
+    pub(crate) fn serve(responses: &[(u16, &str)]) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let responses: Vec<(u16, String)> = responses
+            .iter()
+            .map(|&(status, body)| (status, body.to_owned()))
+            .collect();
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+
+                    let mut request = String::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                            content_length = length.trim().parse().unwrap();
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" || line.is_empty() {
+                            break;
+                        }
+                    }
+
+                    let mut request_body = vec![0; content_length];
+                    reader.read_exact(&mut request_body).unwrap();
+                    request.push_str(&String::from_utf8(request_body).unwrap());
+
+                    let response = format!(
+                        "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    reader.get_mut().write_all(response.as_bytes()).unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+
+    pub(crate) fn test_config(base_url: &str) -> Config {
+        Config {
+            base_url: base_url.to_owned(),
+            session: Some("53616c7465".to_owned()),
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-client-{}", std::process::id()));
+        dir.join(name)
+    }
+
+    #[test]
+    fn it_downloads_inputs_with_the_session_cookie() {
+        let (base_url, server) = serve_once(200, "3,4,3,1,2\n");
+        let client = Client::new(&test_config(&base_url)).unwrap();
+
+        assert_eq!(client.input(2021, 6).unwrap(), "3,4,3,1,2\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2021/day/6/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=53616c7465\r\n"));
+    }
+
+    #[test]
+    fn it_reports_server_errors() {
+        let (base_url, server) = serve_once(400, "Please log in to get your puzzle input.");
+        let client = Client::new(&test_config(&base_url)).unwrap();
+
+        match client.input(2021, 6) {
+            Err(ClientError::Status(400, body)) => assert!(body.contains("log in")),
+            other => panic!("unexpected result {:?}", other),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn it_posts_answers_as_a_form() {
+        let (base_url, server) =
+            serve_once(200, "<article><p>That's the right answer!</p></article>");
+        let client = Client::new(&test_config(&base_url)).unwrap();
+
+        assert!(client
+            .answer(2021, 6, 2, "26984457539")
+            .unwrap()
+            .contains("right answer"));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2021/day/6/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=53616c7465\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=26984457539"));
+    }
+
+    #[test]
+    fn it_needs_a_session() {
+        let config = Config::default();
+        assert!(matches!(
+            Client::new(&config),
+            Err(ClientError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn it_caches_inputs() {
+        let path = temp_path("day06.txt");
+        let _ = fs::remove_file(&path);
+
+        let (base_url, server) = serve_once(200, "3,4,3,1,2\n");
+        let config = test_config(&base_url);
+
+        assert_eq!(
+            fetch_input(&config, 2021, 6, &path).unwrap(),
+            Fetched::Downloaded
+        );
+        server.join().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3,4,3,1,2\n");
+
+        // The server is gone, so this only works if nothing is downloaded
+        assert_eq!(
+            fetch_input(&config, 2021, 6, &path).unwrap(),
+            Fetched::Cached
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn it_downloads_over_an_empty_stub() {
+        let path = temp_path("day14.txt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "").unwrap();
+
+        let (base_url, server) = serve_once(200, "NNCB\n");
+        assert_eq!(
+            fetch_input(&test_config(&base_url), 2021, 14, &path).unwrap(),
+            Fetched::Downloaded
+        );
+        server.join().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "NNCB\n");
+
+        fs::remove_file(&path).unwrap();
+    }
+}
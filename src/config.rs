@@ -0,0 +1,135 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::ParseError;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings to talk to the Advent of Code website.
+///
+/// They are read from a config file with `key = value` lines (`session` and `base_url`), found
+/// at `$AOC_CONFIG`, or `aoc/config` inside `$XDG_CONFIG_HOME` or `~/.config`. The
+/// `AOC_SESSION` and `AOC_BASE_URL` environment variables take precedence over the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    pub base_url: String,
+    /// Value of the `session` cookie of a logged in user
+    pub session: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            session: None,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config file, if there is one, and applies the environment overrides
+    pub fn load() -> Result<Config, Box<dyn Error>> {
+        let mut config = match config_path() {
+            Some(path) => match fs::read_to_string(&path) {
+                Ok(contents) => contents
+                    .parse()
+                    .map_err(|err| format!("{}: {}", path.display(), err))?,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Config::default(),
+                Err(err) => return Err(format!("{}: {}", path.display(), err).into()),
+            },
+            None => Config::default(),
+        };
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+
+        Ok(config)
+    }
+}
+
+impl FromStr for Config {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut config = Config::default();
+
+        for (n, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::new(1, "a `key = value` setting").offset_lines(n))?;
+
+            match key.trim() {
+                "session" => config.session = Some(value.trim().to_owned()),
+                "base_url" => config.base_url = value.trim().to_owned(),
+                _ => {
+                    return Err(ParseError::at(line, key, "`session` or `base_url`").offset_lines(n))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("AOC_CONFIG") {
+        return Some(path.into());
+    }
+
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(config_dir.join("aoc").join("config"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_config_file() {
+        let config: Config =
+            "# Advent of Code\nsession = 53616c7465\nbase_url = http://localhost:8000"
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                base_url: "http://localhost:8000".to_owned(),
+                session: Some("53616c7465".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn it_defaults_to_the_real_website() {
+        let config: Config = "".parse().unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.base_url, DEFAULT_BASE_URL);
+    }
+
+    #[test]
+    fn it_fails_to_parse_unknown_settings() {
+        let err = "session = abc\ntoken = abc".parse::<Config>().err();
+        assert_eq!(
+            err,
+            Some(ParseError::new(1, "`session` or `base_url`").offset_lines(1))
+        );
+    }
+}
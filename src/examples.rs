@@ -0,0 +1,277 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where the example fixtures are kept
+pub const FIXTURES_DIR: &str = "fixtures";
+
+/// The examples found in a saved puzzle description page
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PuzzlePage {
+    /// Contents of every `<pre><code>` block, in page order
+    pub examples: Vec<String>,
+    /// Expected answer for the example of every part found in the page
+    pub answers: Vec<String>,
+}
+
+impl PuzzlePage {
+    /// Keeps only the example blocks numbered in `blocks`, from 1 in page order, in the order
+    /// given. Fails if the page has no such block.
+    pub fn pick(&mut self, blocks: &[usize]) -> Result<(), String> {
+        self.examples = blocks
+            .iter()
+            .map(|&block| {
+                block
+                    .checked_sub(1)
+                    .and_then(|i| self.examples.get(i))
+                    .cloned()
+                    .ok_or_else(|| {
+                        format!(
+                            "no block {} in the page, it has {}",
+                            block,
+                            self.examples.len()
+                        )
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    /// Keeps only the example blocks that `is_input` accepts, dropping the diagrams and the
+    /// walkthroughs the page also shows in `<pre><code>` blocks
+    pub fn retain_inputs<F: Fn(&str) -> bool>(&mut self, is_input: F) {
+        self.examples.retain(|example| is_input(example));
+    }
+}
+
+/// Extracts the example blocks and the expected answers from the HTML of a puzzle page.
+///
+/// The expected answer of a part is the last `<code><em>` in its `<article>`, which is how the
+/// puzzle descriptions end their example walkthroughs.
+pub fn parse_page(html: &str) -> PuzzlePage {
+    let mut page = PuzzlePage::default();
+
+    for article in between(html, "<article", "</article>") {
+        page.examples.extend(
+            between(article, "<pre><code>", "</code></pre>")
+                .into_iter()
+                .map(text),
+        );
+
+        if let Some(answer) = between(article, "<code><em>", "</em></code>").last() {
+            page.answers.push(text(answer));
+        }
+    }
+
+    page
+}
+
+/// Writes the examples in `page` as `exampleN.txt` files inside `dir`, along with their
+/// expected answers in `answers.txt` (in the format of the answers manifest)
+pub fn write_fixtures(
+    page: &PuzzlePage,
+    year: u16,
+    day: u8,
+    dir: &Path,
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut written = vec![];
+
+    for (n, example) in page.examples.iter().enumerate() {
+        let path = dir.join(format!("example{}.txt", n + 1));
+        fs::write(&path, example)?;
+        written.push(path);
+    }
+
+    if !page.answers.is_empty() {
+        let answers: String = page
+            .answers
+            .iter()
+            .enumerate()
+            .map(|(part, answer)| format!("{}/{:02}.{}: {}\n", year, day, part + 1, answer))
+            .collect();
+
+        let path = dir.join("answers.txt");
+        fs::write(&path, answers)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// Fixtures directory for a day of a year, like `fixtures/2021/day05`
+pub fn fixtures_dir(year: u16, day: u8) -> PathBuf {
+    Path::new(FIXTURES_DIR)
+        .join(year.to_string())
+        .join(format!("day{:02}", day))
+}
+
+/// Every piece of `html` found between `start` and `end`, in order
+fn between<'a>(html: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = html;
+
+    while let Some(i) = rest.find(start) {
+        rest = &rest[i + start.len()..];
+        match rest.find(end) {
+            Some(j) => {
+                found.push(&rest[..j]);
+                rest = &rest[j + end.len()..];
+            }
+            None => break,
+        }
+    }
+
+    found
+}
+
+/// Text content of a piece of HTML: drops the tags and decodes the entities
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for chr in html.chars() {
+        match chr {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(chr),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::{Answers, Check};
+    use crate::options::Options;
+    use crate::solution::YEARS;
+
+    const TEST_PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 10: Syntax Scoring ---</h2>
+<p>For example, consider the following navigation subsystem:</p>
+<pre><code>[({(&lt;(())[]&gt;[[{[]{&lt;()&lt;&gt;&gt;
+[(()[&lt;&gt;])]({[&lt;{&lt;&lt;[]&gt;&gt;(
+</code></pre>
+<p>Some of the lines aren't corrupted, just incomplete; you can ignore these lines for now.</p>
+<p>In the above example, an illegal <code>)</code> was found twice (<code>2*3 = <em>6</em></code> points), an illegal <code>]</code> was found once (<code><em>57</em></code> points), for a total of <code><em>26397</em></code> points!</p>
+</article>
+<p>Your puzzle answer was <code>311949</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In the example above, the middle score is <code><em>288957</em></code> because there are the same number of scores smaller and larger than it.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn it_extracts_examples_and_answers() {
+        let page = parse_page(TEST_PAGE);
+
+        assert_eq!(
+            page.examples,
+            vec!["[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(\n"]
+        );
+        assert_eq!(page.answers, vec!["26397", "288957"]);
+    }
+
+    #[test]
+    fn it_writes_fixtures() {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let page = parse_page(TEST_PAGE);
+
+        let written = write_fixtures(&page, 2021, 10, &dir).unwrap();
+        assert_eq!(
+            written,
+            vec![dir.join("example1.txt"), dir.join("answers.txt")]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("answers.txt")).unwrap(),
+            "2021/10.1: 26397\n2021/10.2: 288957\n"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// The blocks of the day 8 page, where the first one draws the segments of the digits
+    const DIAGRAM_PAGE: &str = r#"<article class="day-desc"><h2>--- Day 8: Seven Segment Search ---</h2>
+<pre><code>  0:      1:
+ aaaa    ....
+b    c  .    c
+</code></pre>
+<pre><code>acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf
+</code></pre>
+<pre><code>be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+</code></pre>
+<pre><code>fdgacbe cefdb cefbgd gcbe: 8394
+</code></pre>
+<p>In the output values, how many times do digits 1, 4, 7, or 8 appear? <code><em>26</em></code></p>
+</article>"#;
+
+    #[test]
+    fn it_skips_blocks_that_are_not_inputs() {
+        use crate::year2021::day08::Day08;
+        use crate::Solution;
+
+        let mut page = parse_page(DIAGRAM_PAGE);
+        assert_eq!(page.examples.len(), 4);
+
+        page.retain_inputs(|example| Day08::parse(example).is_ok());
+        assert_eq!(page.examples.len(), 2);
+        assert!(page.examples[0].starts_with("acedgfb"));
+        assert!(page.examples[1].starts_with("be cfbegad"));
+    }
+
+    #[test]
+    fn it_picks_blocks() {
+        let mut page = parse_page(DIAGRAM_PAGE);
+        page.pick(&[3, 2]).unwrap();
+        assert_eq!(page.examples.len(), 2);
+        assert!(page.examples[0].starts_with("be cfbegad"));
+        assert!(page.examples[1].starts_with("acedgfb"));
+
+        let mut page = parse_page(DIAGRAM_PAGE);
+        assert_eq!(
+            page.pick(&[5]),
+            Err("no block 5 in the page, it has 4".to_owned())
+        );
+        assert!(page.pick(&[0]).is_err());
+    }
+
+    #[test]
+    fn every_day_solves_its_first_example() {
+        for &(year, listed) in YEARS {
+            for &(day, solve, _) in listed.days {
+                // Days scaffolded with `aoc new` have no answers to check until they're fetched
+                let dir = fixtures_dir(year, day);
+                let answers = match fs::read_to_string(dir.join("answers.txt")) {
+                    Ok(answers) => answers.parse::<Answers>().unwrap(),
+                    Err(_) => continue,
+                };
+                let example = fs::read_to_string(dir.join("example1.txt")).unwrap();
+
+                let solved = solve(&mut example.as_bytes(), &Options::default()).unwrap();
+                for part in solved.parts {
+                    let answer = part
+                        .answer
+                        .unwrap_or_else(|err| panic!("{}/{:02}.{}: {}", year, day, part.part, err));
+                    let check = answers.check(year, day, part.part, &answer);
+                    assert!(
+                        matches!(check, Check::Correct | Check::Unknown),
+                        "{}/{:02}.{}: {:?}, got {}",
+                        year,
+                        day,
+                        part.part,
+                        check,
+                        answer
+                    );
+                }
+            }
+        }
+    }
+}
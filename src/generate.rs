@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use crate::solution::find_year;
+
+/// Writes a random input of the given size, at least 1, for a day
+pub type Generator = fn(&mut Rng, usize, &mut dyn Write) -> io::Result<()>;
+
+/// A day with a generator, and the size of a typical puzzle input. What the size counts depends
+/// on the day: lines, numbers, boards, dots, or the side of a square grid.
+pub type Entry = (u8, Generator, usize);
+
+/// The generator for a day of `year` and its default size
+pub fn generator(year: u16, day: u8) -> Option<(Generator, usize)> {
+    find_year(year)?
+        .generators
+        .iter()
+        .find(|(n, _, _)| *n == day)
+        .map(|&(_, generator, size)| (generator, size))
+}
+
+/// Generates a whole input for a day of `year` in memory
+pub fn generate(year: u16, day: u8, size: usize, seed: u64) -> Option<String> {
+    let (generator, _) = generator(year, day)?;
+    let mut input = vec![];
+    generator(&mut Rng::new(seed), size, &mut input).unwrap();
+    Some(String::from_utf8(input).unwrap())
+}
+
+/// Small pseudo-random number generator (SplitMix64). The same seed always gives the same
+/// numbers, on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` to `n - 1`
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot pick a number below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// A number from `low` to `high`, both included
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    /// True once every `n` times, on average
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// `count` distinct values taken from `next`, in the order they were made
+pub(crate) fn distinct<T: Clone + Eq + std::hash::Hash>(
+    count: usize,
+    mut next: impl FnMut() -> T,
+) -> Vec<T> {
+    let mut seen = HashSet::with_capacity(count);
+    let mut values = Vec::with_capacity(count);
+
+    while values.len() < count {
+        let value = next();
+        if seen.insert(value.clone()) {
+            values.push(value);
+        }
+    }
+
+    values
+}
+
+pub(crate) fn join<T: ToString>(values: &[T], separator: &str) -> String {
+    let values: Vec<String> = values.iter().map(T::to_string).collect();
+    values.join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::Options;
+    use crate::solution::{days, YEARS};
+    use crate::year2021::day11::SyncError;
+
+    /// Every day of every year with a generator
+    fn generated_days() -> impl Iterator<Item = (u16, u8)> {
+        YEARS.iter().flat_map(|(year, listed)| {
+            listed
+                .generators
+                .iter()
+                .map(move |&(day, _, _)| (*year, day))
+        })
+    }
+
+    #[test]
+    fn it_generates_inputs_every_day_can_solve() {
+        for (year, day) in generated_days() {
+            let (_, solve, _) = days(year)
+                .and_then(|days| days.iter().find(|(n, _, _)| *n == day))
+                .unwrap();
+
+            // Telling that a big octopus grid never synchronizes takes a while
+            let size = if day == 11 { 10 } else { 50 };
+            for seed in 0..5 {
+                let input = generate(year, day, size, seed).unwrap();
+                let solved = solve(&mut input.as_bytes(), &Options::default())
+                    .unwrap_or_else(|err| panic!("day {} with seed {}: {}", day, seed, err));
+
+                for part in solved.parts {
+                    match part.answer {
+                        // Most random octopus grids never flash all at once, and part 2 says so
+                        Err(err) if (year, day, part.part) == (2021, 11, 2) => {
+                            assert_eq!(err, SyncError::Cycle.to_string(), "seed {}", seed)
+                        }
+                        Err(err) => panic!(
+                            "day {}.{} with seed {}: {}\n{}",
+                            day, part.part, seed, err, input
+                        ),
+                        Ok(_) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_is_reproducible_from_the_seed() {
+        for (year, day) in generated_days() {
+            assert_eq!(generate(year, day, 20, 7), generate(year, day, 20, 7));
+            assert_ne!(generate(year, day, 20, 7), generate(year, day, 20, 8));
+        }
+        assert_eq!(generate(2021, 12, 20, 7), None);
+    }
+
+    #[test]
+    fn it_generates_the_requested_size() {
+        let lines = |day| generate(2021, day, 30, 1).unwrap().lines().count();
+
+        assert_eq!(lines(1), 30);
+        assert_eq!(lines(4), 1 + 30 * 6);
+        assert_eq!(lines(11), 30);
+        assert_eq!(generate(2021, 6, 30, 1).unwrap().split(',').count(), 30);
+    }
+
+    #[test]
+    fn it_picks_numbers_in_range() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 6];
+
+        for _ in 0..1000 {
+            let n = rng.between(3, 8);
+            assert!((3..=8).contains(&n));
+            seen[n as usize - 3] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on the plane. `y` grows downwards, like the rows of the puzzle inputs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    /// Distance moving only along the axes
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance moving along the axes and diagonals, like a king in chess
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Point with the sign of each coordinate, like a single step towards `self` from the origin
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The next point in `direction`
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+
+    /// The 4 points next to this one, up, right, down and left
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL
+            .into_iter()
+            .map(move |dir| self + dir.offset())
+    }
+
+    /// The 8 points around this one, clockwise from the one above
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL
+            .into_iter()
+            .zip(Diagonal::ALL)
+            .flat_map(|(dir, diagonal)| [dir.offset(), diagonal.offset()])
+            .map(move |offset| self + offset)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// One of the 4 orthogonal directions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from `Up`
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The step to take to move once in this direction
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// One of the 4 diagonal directions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Diagonal {
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
+}
+
+impl Diagonal {
+    /// Every diagonal, clockwise from `UpRight`
+    pub const ALL: [Diagonal; 4] = [
+        Diagonal::UpRight,
+        Diagonal::DownRight,
+        Diagonal::DownLeft,
+        Diagonal::UpLeft,
+    ];
+
+    /// The step to take to move once in this direction
+    pub fn offset(self) -> Point {
+        match self {
+            Diagonal::UpRight => Point::new(1, -1),
+            Diagonal::DownRight => Point::new(1, 1),
+            Diagonal::DownLeft => Point::new(-1, 1),
+            Diagonal::UpLeft => Point::new(-1, -1),
+        }
+    }
+}
+
+/// A `width` by `height` rectangle with its top left corner at the origin
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Bounds {
+    pub fn new(width: usize, height: usize) -> Bounds {
+        Bounds { width, height }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as u64) < self.width as u64
+            && (point.y as u64) < self.height as u64
+    }
+
+    /// Brings `point` inside the bounds as if the opposite edges were stitched together
+    pub fn wrap(&self, point: Point) -> Point {
+        Point::new(
+            point.x.rem_euclid(self.width as i64),
+            point.y.rem_euclid(self.height as i64),
+        )
+    }
+
+    /// The orthogonal neighbours of `point` inside the bounds
+    pub fn neighbours4(self, point: Point) -> impl Iterator<Item = Point> {
+        point.neighbours4().filter(move |&p| self.contains(p))
+    }
+
+    /// The orthogonal and diagonal neighbours of `point` inside the bounds
+    pub fn neighbours8(self, point: Point) -> impl Iterator<Item = Point> {
+        point.neighbours8().filter(move |&p| self.contains(p))
+    }
+
+    /// The orthogonal neighbours of `point`, wrapping around the edges
+    pub fn neighbours4_wrapping(self, point: Point) -> impl Iterator<Item = Point> {
+        point.neighbours4().map(move |p| self.wrap(p))
+    }
+
+    /// The orthogonal and diagonal neighbours of `point`, wrapping around the edges
+    pub fn neighbours8_wrapping(self, point: Point) -> impl Iterator<Item = Point> {
+        point.neighbours8().map(move |p| self.wrap(p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_measures_distances() {
+        let (a, b) = (Point::new(1, -2), Point::new(-3, 5));
+
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(a.manhattan(a), 0);
+    }
+
+    #[test]
+    fn it_turns_around() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert_eq!(Point::ORIGIN.step(Direction::Up), Point::new(0, -1));
+    }
+
+    #[test]
+    fn it_clips_neighbours_to_the_bounds() {
+        let bounds = Bounds::new(3, 2);
+        let corner = Point::new(0, 0);
+
+        assert_eq!(
+            bounds.neighbours4(corner).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(
+            bounds.neighbours8(corner).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(1, 1), Point::new(0, 1)]
+        );
+        assert_eq!(bounds.neighbours8(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn it_wraps_neighbours_around_the_edges() {
+        let bounds = Bounds::new(3, 2);
+
+        assert_eq!(
+            bounds
+                .neighbours4_wrapping(Point::new(0, 0))
+                .collect::<Vec<_>>(),
+            vec![
+                Point::new(0, 1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(2, 0)
+            ]
+        );
+        assert_eq!(bounds.neighbours8_wrapping(Point::new(2, 1)).count(), 8);
+    }
+}
@@ -0,0 +1,319 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geom::{Bounds, Point};
+use crate::parse::{parse_lines, ParseError};
+
+/// A rectangular grid of cells, stored row after row in a single `Vec`.
+///
+/// Cells are addressed by points, where `x` is the column and `y` the row, both starting from the
+/// top left corner.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of the given size with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Grid made of `rows`, which must all be as wide as the first one
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a grid with a cell per character, like `#..#`. `cell` turns a character into a
+    /// cell, or returns `None` if it's not `expected`.
+    pub fn parse_chars<F>(input: &str, expected: &str, cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        parse_grid(input, expected, "characters", cell)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds::new(self.width, self.height)
+    }
+
+    /// Whether `point` is inside the grid
+    pub fn contains(&self, point: Point) -> bool {
+        self.bounds().contains(point)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        let index = self.index_of(point)?;
+        Some(&self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        let index = self.index_of(point)?;
+        Some(&mut self.cells[index])
+    }
+
+    /// The orthogonal neighbours of `point` inside the grid
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.bounds().neighbours4(point)
+    }
+
+    /// The orthogonal and diagonal neighbours of `point` inside the grid
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.bounds().neighbours8(point)
+    }
+
+    /// Row `y`, from left to right
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// Column `x`, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every column, from left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Every point in the grid, row after row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.len()).map(move |i| point_at(i, width))
+    }
+
+    /// Every cell along with its point, row after row
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        (self.cells.iter().enumerate()).map(move |(i, cell)| (point_at(i, width), cell))
+    }
+
+    /// Every cell along with its point, row after row
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        let width = self.width;
+        (self.cells.iter_mut().enumerate()).map(move |(i, cell)| (point_at(i, width), cell))
+    }
+
+    /// Grid of the same size with `f` applied to every cell
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// Point of the cell at `index` in a grid `width` cells wide
+fn point_at(index: usize, width: usize) -> Point {
+    Point::new((index % width) as i64, (index / width) as i64)
+}
+
+impl Grid<u8> {
+    /// Parses a grid of single digits, like `2199943210` on every line
+    pub fn parse_digits(input: &str) -> Result<Grid<u8>, ParseError> {
+        parse_grid(input, "a digit", "digits", |chr| {
+            chr.to_digit(10).map(|digit| digit as u8)
+        })
+    }
+}
+
+/// Parses a grid with a cell per character, making sure every row has the same width
+fn parse_grid<T, F>(
+    input: &str,
+    expected: &str,
+    cells: &str,
+    cell: F,
+) -> Result<Grid<T>, ParseError>
+where
+    F: Fn(char) -> Option<T>,
+{
+    let width = input.lines().next().map_or(0, |line| line.chars().count());
+
+    let rows = parse_lines(input, |line| {
+        let row = line
+            .chars()
+            .enumerate()
+            .map(|(col, chr)| cell(chr).ok_or_else(|| ParseError::new(col + 1, expected)))
+            .collect::<Result<Vec<T>, ParseError>>()?;
+
+        if row.len() != width {
+            return Err(ParseError::new(
+                row.len().min(width) + 1,
+                format!("a row of {} {}", width, cells),
+            ));
+        }
+
+        Ok(row)
+    })?;
+
+    // Every row was checked to be as wide as the first one
+    Ok(Grid::from_rows(rows).unwrap())
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.index_of(point) {
+            Some(index) => &self.cells[index],
+            None => panic!(
+                "point {} out of a {}x{} grid",
+                point, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        match self.index_of(point) {
+            Some(index) => &mut self.cells[index],
+            None => panic!(
+                "point {} out of a {}x{} grid",
+                point, self.width, self.height
+            ),
+        }
+    }
+}
+
+/// Draws the grid with every cell next to each other, and a line per row
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_digit_grids() {
+        let grid = Grid::parse_digits("123\n456").unwrap();
+        assert_eq!(
+            grid,
+            Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+        );
+
+        assert_eq!(
+            Grid::parse_digits("123\n4x6"),
+            Err(ParseError::new(2, "a digit").offset_lines(1))
+        );
+        assert_eq!(
+            Grid::parse_digits("123\n4567"),
+            Err(ParseError::new(4, "a row of 3 digits").offset_lines(1))
+        );
+    }
+
+    #[test]
+    fn it_parses_character_maps() {
+        let cell = |chr| match chr {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+
+        let grid = Grid::parse_chars("#..\n.#.", "`#` or `.`", cell).unwrap();
+        assert_eq!(
+            grid.map(|&wall| if wall { 'X' } else { ' ' }).to_string(),
+            "X  \n X \n"
+        );
+
+        assert_eq!(
+            Grid::parse_chars("#..\n.#", "`#` or `.`", cell),
+            Err(ParseError::new(3, "a row of 3 characters").offset_lines(1))
+        );
+    }
+
+    #[test]
+    fn it_iterates_rows_columns_and_cells() {
+        let grid = Grid::parse_digits("123\n456").unwrap();
+
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![&2, &5]);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(
+            grid.cells()
+                .filter(|(_, &n)| n % 2 == 0)
+                .collect::<Vec<_>>(),
+            vec![
+                (Point::new(1, 0), &2),
+                (Point::new(0, 1), &4),
+                (Point::new(2, 1), &6)
+            ]
+        );
+    }
+
+    #[test]
+    fn it_checks_bounds() {
+        let mut grid = Grid::new(3, 2, 0);
+
+        assert!(grid.contains(Point::new(2, 1)));
+        assert!(!grid.contains(Point::new(3, 0)));
+        assert!(!grid.contains(Point::new(-1, 0)));
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+
+        grid[Point::new(2, 1)] = 7;
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&7));
+        assert_eq!(grid.neighbours8(Point::new(2, 1)).count(), 3);
+        assert_eq!(grid.to_string(), "000\n007\n");
+    }
+}
@@ -1,18 +1,391 @@
-use std::env;
+use std::error::Error;
+use std::fmt;
 use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
 
-pub fn read_input() -> Result<String, std::io::Error> {
-    let input = env::args().nth(1).unwrap_or_else(|| "-".to_owned());
+use flate2::bufread::MultiGzDecoder;
+use ruzstd::decoding::StreamingDecoder;
 
-    // Try reading from file
-    let mut file: Box<dyn std::io::Read> = if input == "-" {
-        Box::new(std::io::stdin())
-    } else {
-        Box::new(File::open(input)?)
-    };
+use crate::parse::ParseError;
 
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
+/// Where the puzzle inputs are kept
+pub const INPUTS_DIR: &str = "inputs";
 
-    Ok(contents)
+/// Default input file for a day of a year, like `inputs/2021/day05.txt`
+pub fn input_path(year: u16, day: u8) -> PathBuf {
+    Path::new(INPUTS_DIR)
+        .join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+/// Where an input is read from: a file, or stdin when given as `-`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    pub fn new<P: AsRef<Path>>(path: P) -> Source {
+        let path = path.as_ref();
+        if path == Path::new("-") {
+            Source::Stdin
+        } else {
+            Source::File(path.to_owned())
+        }
+    }
+
+    /// Buffered reader over the whole input, decompressing it on the fly if needed
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        let (mut reader, path): (Box<dyn BufRead>, _) = match self {
+            Source::Stdin => (Box::new(io::stdin().lock()), None),
+            Source::File(path) => (
+                Box::new(BufReader::new(File::open(path)?)),
+                Some(path.as_path()),
+            ),
+        };
+
+        Ok(match Compression::detect(path, reader.fill_buf()?) {
+            Compression::None => reader,
+            Compression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(reader))),
+            Compression::Zstd => {
+                let decoder = StreamingDecoder::new(reader)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                Box::new(BufReader::new(decoder))
+            }
+        })
+    }
+
+    /// Reads the whole input into memory
+    pub fn read_to_string(&self) -> io::Result<String> {
+        let mut contents = String::new();
+        self.open()?.read_to_string(&mut contents)?;
+        Ok(contents)
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "-"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Compression formats inputs can be kept in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Tells the compression of a file from the extension of its `path`, if known, or else from
+    /// its first bytes
+    pub fn detect(path: Option<&Path>, header: &[u8]) -> Compression {
+        let extension = path.and_then(Path::extension).and_then(|ext| ext.to_str());
+
+        match extension {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            _ if header.starts_with(&[0x1f, 0x8b]) => Compression::Gzip,
+            _ if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+}
+
+/// Reads the whole input from `path`, or from stdin if `path` is `-`
+pub fn read_input_from<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    Source::new(path).read_to_string()
+}
+
+/// Why an input could not be read: either reading it failed, or what was read can't be parsed
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "{}", err),
+            ReadError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(err: ParseError) -> Self {
+        ReadError::Parse(err)
+    }
+}
+
+/// Streaming version of `parse::parse_lines`: parses every line of `reader` with `parse` as it is
+/// read, without loading the whole input in memory
+pub fn read_lines<T, F, R>(reader: R, mut parse: F) -> Result<Vec<T>, ReadError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .map(|(n, line)| Ok(parse(&line?).map_err(|err| err.offset_lines(n))?))
+        .collect()
+}
+
+/// Streaming version of `sections`: splits what is read from `reader` in sections as it goes
+pub fn read_sections<R: BufRead>(reader: R) -> SectionReader<R> {
+    SectionReader {
+        lines: reader.lines(),
+        line: 0,
+    }
+}
+
+/// A block of consecutive non-blank lines
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Section<T> {
+    /// How many lines of the input come before the section, to be used with
+    /// `ParseError::offset_lines`
+    pub line: usize,
+    pub text: T,
+}
+
+/// Splits `input` in the sections separated by one or more blank lines
+pub fn sections(input: &str) -> impl Iterator<Item = Section<&str>> {
+    let mut lines = input.split_inclusive('\n').enumerate().peekable();
+
+    std::iter::from_fn(move || {
+        // Skip the blank lines before the section
+        while lines.next_if(|(_, line)| line.trim().is_empty()).is_some() {}
+
+        let &(line, first) = lines.peek()?;
+        let start = first.as_ptr() as usize - input.as_ptr() as usize;
+        let mut end = start;
+
+        while let Some((_, text)) = lines.next_if(|(_, line)| !line.trim().is_empty()) {
+            end += text.len();
+        }
+
+        let text = input[start..end].trim_end_matches(['\n', '\r']);
+        Some(Section { line, text })
+    })
+}
+
+/// Sections of a reader, see `read_sections`
+pub struct SectionReader<R> {
+    lines: io::Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> Iterator for SectionReader<R> {
+    type Item = io::Result<Section<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut section: Option<Section<String>> = None;
+
+        for line in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err)),
+            };
+            self.line += 1;
+
+            match (&mut section, line.trim().is_empty()) {
+                (None, true) => continue,
+                (None, false) => {
+                    section = Some(Section {
+                        line: self.line - 1,
+                        text: line,
+                    })
+                }
+                (Some(section), false) => {
+                    section.text.push('\n');
+                    section.text.push_str(&line);
+                }
+                (Some(_), true) => break,
+            }
+        }
+
+        section.map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse_number;
+
+    const TEST_INPUT: &str = "7,4,9\n\n22 13\n 8  2\n\n\n 1 24\n";
+
+    #[test]
+    fn it_splits_sections() {
+        let found: Vec<_> = sections(TEST_INPUT).collect();
+        assert_eq!(
+            found,
+            vec![
+                Section {
+                    line: 0,
+                    text: "7,4,9"
+                },
+                Section {
+                    line: 2,
+                    text: "22 13\n 8  2"
+                },
+                Section {
+                    line: 6,
+                    text: " 1 24"
+                },
+            ]
+        );
+
+        assert_eq!(sections("\n \n").count(), 0);
+    }
+
+    #[test]
+    fn it_streams_sections() {
+        let streamed: Vec<_> = read_sections(TEST_INPUT.as_bytes())
+            .map(Result::unwrap)
+            .collect();
+        let expected: Vec<_> = sections(TEST_INPUT)
+            .map(|section| Section {
+                line: section.line,
+                text: section.text.to_owned(),
+            })
+            .collect();
+
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn it_detects_compression() {
+        let gzip = [0x1f, 0x8b, 0x08];
+        let zstd = [0x28, 0xb5, 0x2f, 0xfd];
+
+        assert_eq!(Compression::detect(None, &gzip), Compression::Gzip);
+        assert_eq!(Compression::detect(None, &zstd), Compression::Zstd);
+        assert_eq!(Compression::detect(None, b"3,4,3"), Compression::None);
+        assert_eq!(
+            Compression::detect(Some(Path::new("day05.txt.zst")), b""),
+            Compression::Zstd
+        );
+        assert_eq!(
+            Compression::detect(Some(Path::new("day05.txt")), &gzip),
+            Compression::Gzip
+        );
+    }
+
+    #[test]
+    fn it_reads_compressed_inputs() {
+        use flate2::write::GzEncoder;
+        use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+        use std::io::Write;
+
+        let dir = std::env::temp_dir().join(format!("aoc-compressed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut gzip = GzEncoder::new(vec![], flate2::Compression::default());
+        gzip.write_all(TEST_INPUT.as_bytes()).unwrap();
+        let gzip = gzip.finish().unwrap();
+        let zstd = compress_to_vec(TEST_INPUT.as_bytes(), CompressionLevel::Fastest);
+
+        // Detected by extension, and by magic bytes when the name doesn't tell
+        for (name, contents) in [
+            ("input.gz", &gzip),
+            ("gzip.txt", &gzip),
+            ("input.zst", &zstd),
+            ("zstd.txt", &zstd),
+        ] {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).unwrap();
+            assert_eq!(read_input_from(&path).unwrap(), TEST_INPUT, "{}", name);
+        }
+
+        let sections = read_sections(Source::new(dir.join("input.zst")).open().unwrap());
+        assert_eq!(sections.count(), 3);
+
+        std::fs::write(dir.join("broken.gz"), TEST_INPUT).unwrap();
+        assert!(read_input_from(dir.join("broken.gz")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn it_streams_inputs_bigger_than_a_buffer() {
+        use flate2::write::GzEncoder;
+        use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+        use std::io::Write;
+
+        let dir = std::env::temp_dir().join(format!("aoc-streamed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Many times the 8 KiB a `BufReader` holds, with sections of a few lines each
+        let input: String = (0..20_000)
+            .map(|n| {
+                if n % 5 == 4 {
+                    "\n".to_owned()
+                } else {
+                    format!("{}\n", n)
+                }
+            })
+            .collect();
+        let mut gzip = GzEncoder::new(vec![], flate2::Compression::default());
+        gzip.write_all(input.as_bytes()).unwrap();
+        let gzip = gzip.finish().unwrap();
+        let zstd = compress_to_vec(input.as_bytes(), CompressionLevel::Fastest);
+
+        let expected_lines: Vec<&str> = input.lines().collect();
+        let expected_sections: Vec<Section<String>> = sections(&input)
+            .map(|section| Section {
+                line: section.line,
+                text: section.text.to_owned(),
+            })
+            .collect();
+        assert_eq!(expected_sections.len(), 4000);
+
+        for (name, contents) in [
+            ("input.txt", input.as_bytes()),
+            ("input.gz", &gzip),
+            ("input.zst", &zstd),
+        ] {
+            let source = Source::new(dir.join(name));
+            std::fs::write(dir.join(name), contents).unwrap();
+
+            let lines: Vec<String> =
+                read_lines(source.open().unwrap(), |line| Ok(line.to_owned())).unwrap();
+            assert_eq!(lines, expected_lines, "{}", name);
+            let streamed: Vec<_> = read_sections(source.open().unwrap())
+                .map(Result::unwrap)
+                .collect();
+            assert_eq!(streamed, expected_sections, "{}", name);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn it_reads_lines_with_their_line_numbers() {
+        let numbers = read_lines("3\n4\n".as_bytes(), |line| parse_number::<u32>(line, line));
+        assert_eq!(numbers.unwrap(), vec![3, 4]);
+
+        let err = read_lines("3\nfour\n".as_bytes(), |line| {
+            parse_number::<u32>(line, line)
+        });
+        match err {
+            Err(ReadError::Parse(err)) => assert_eq!(err.line, 2),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
 }
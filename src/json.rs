@@ -0,0 +1,58 @@
+//! Just enough JSON to print results, without pulling in a serializer
+
+/// `text` as a JSON string literal, quotes included
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+
+    for chr in text.chars() {
+        match chr {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            chr if chr.is_control() => quoted.push_str(&format!("\\u{:04x}", chr as u32)),
+            chr => quoted.push(chr),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// JSON object with the given fields, whose values must be JSON already
+pub fn object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}: {}", quote(name), value))
+        .collect();
+
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// JSON array of values that are JSON already
+pub fn array(values: &[String]) -> String {
+    format!("[{}]", values.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_quotes_strings() {
+        assert_eq!(quote("#..#\n####"), r##""#..#\n####""##);
+        assert_eq!(quote(r#"say "hi" \o/"#), r#""say \"hi\" \\o/""#);
+        assert_eq!(quote("\u{1b}[2J"), r#""\u001b[2J""#);
+    }
+
+    #[test]
+    fn it_builds_objects_and_arrays() {
+        let parts = array(&["1".to_owned(), "2".to_owned()]);
+        assert_eq!(
+            object(&[("day", "6".to_owned()), ("parts", parts)]),
+            r#"{"day": 6, "parts": [1, 2]}"#
+        );
+    }
+}
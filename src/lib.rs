@@ -1 +1,25 @@
+pub mod animate;
+pub mod answers;
+pub mod client;
+pub mod config;
+pub mod examples;
+pub mod generate;
+pub mod geom;
+pub mod grid;
 pub mod input;
+pub mod json;
+pub mod options;
+pub mod parallel;
+pub mod parse;
+pub mod repl;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod visual;
+pub mod watch;
+
+pub mod year2021;
+
+pub use options::Params;
+pub use parse::ParseError;
+pub use solution::{Answer, Solution};
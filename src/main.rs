@@ -1,5 +1,1390 @@
-pub mod input;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::process::ExitCode;
+use std::process::{Command, Output};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-fn main() {
-    println!("Hello, world!");
+use aoc::animate::play;
+use aoc::answers::{Answers, Check};
+use aoc::client::{fetch_input, Client, Fetched};
+use aoc::config::Config;
+use aoc::examples::{fixtures_dir, parse_page, write_fixtures};
+use aoc::generate::{generator, Rng};
+use aoc::input::{input_path, read_input_from, Source};
+use aoc::json;
+use aoc::options::{take_flag, take_option, Format, Options, Part};
+use aoc::parallel;
+use aoc::repl::{repl, Reply};
+use aoc::scaffold::scaffold;
+use aoc::solution::{
+    days, latest_year, Day, SolveError, Solved, SolvedPart, Solver, CALENDARS, YEARS,
+};
+use aoc::submit::{self, Attempts, Clock, Outcome, Verdict};
+use aoc::visual::{check_scale, visual, write_gif, write_pngs, DrawError, Frame, Palette};
+use aoc::watch::{TestSummary, Watcher};
+use aoc::Answer;
+
+/// The year and day of a puzzle
+type Date = (u16, u8);
+
+/// How often `aoc watch` looks for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Manifest with the known answers for the committed inputs
+const ANSWERS: &str = "answers.txt";
+
+/// Log of the answers given to the website
+const ATTEMPTS: &str = "attempts.txt";
+
+const USAGE: &str = "Usage:
+    aoc run <days> [inputs] [options]
+                                Solve the given days
+    aoc verify [days]           Check the answers for the given days (default: all) against the
+                                known ones in `answers.txt`
+    aoc fetch <day>             Download the input for a day into `inputs/YYYY/`, unless it is
+                                there already. Needs a session token, see `aoc::config`.
+    aoc submit <day> <part> [input] [--answer A] [--<name> <value>]
+                                Solve a part and send its answer to the website, unless the log
+                                of previous attempts in `attempts.txt` tells how it would go
+                                already. Waits as long as the website asks between answers.
+                                `--answer` sends the given answer instead, like the letters
+                                drawn by day 13.
+    aoc examples <day> <page> [--blocks N,M,...]
+                                Extract the examples and their answers from a saved puzzle page
+                                into `fixtures/YYYY/dayXX/`. The examples are the `<pre><code>`
+                                blocks the day can parse, or the blocks numbered in `--blocks`
+                                (from 1, in page order), as `example1.txt` and on in that order.
+    aoc watch <day> [input]     Re-run the tests and the solution of a day every time its source
+                                or input change
+    aoc new <day>               Create the source, binary, input stub and example fixture of a
+                                new day, and list it wherever days are listed. The first day of a
+                                year starts the year too.
+    aoc gen <day> [--size N] [--seed S]
+                                Print a random input for a day. The same seed always gives the
+                                same input (default: 0). The size is the amount of lines,
+                                numbers, boards or dots, or the side of the grid, depending on the
+                                day, and at least 1 (default: as big as the puzzle input).
+    aoc draw <day> [input] [--out FILE.gif|DIR] [--colours C1,C2,...] [--scale N] [--delay MS]
+                                Record the frames of a day while it runs, and write them as an
+                                animated GIF (default: `YYYY-dayXX.gif`) or as PNG images in a
+                                directory. The colours, like `000000,ffcc00`, are blended over the
+                                levels the day draws. Each cell takes `scale` pixels on each side
+                                (default: to fit about 512 pixels), and each frame shows for
+                                `delay` milliseconds (default: 100). Days 4, 5, 9, 11 and 13 can
+                                be drawn.
+    aoc draw <day> [input] --animate [--colours C1,C2,...] [--delay MS]
+                                Play the frames of a day in the terminal instead. Space pauses,
+                                `n` and `p` step through the frames, `+` and `-` change the speed
+                                and `q` quits.
+    aoc repl <day> [input]      Parse the input of a day once, and explore it with commands read
+                                line by line, like `fold 2` or `basin 3,4`. `help` lists the
+                                commands of the day. Days 4, 9, 11 and 13 can be explored.
+
+<day> is a day of a year, like `2021/5`, or a day of the latest year solved, like `5`.
+<days> can be a single day (`5`), an inclusive range (`3..9`) or `all`, after a year like
+`2021/3..9` or of the latest year solved. `all` alone is every day of every year.
+[inputs] are only allowed when running a single day, and default to `inputs/YYYY/dayXX.txt`.
+Several inputs are solved in the same run, each on its own. Use `-` to read from stdin.
+
+Options:
+    --part 1|2|both             Parts to solve (default: both)
+    --format text|json          Print the answers as text (default), or as a JSON array with an
+                                object per day, holding the type of each answer and the time
+                                taken to parse and solve each part
+    --<name> <value>            Puzzle parameter, like `--days 80` for day 6 or `--steps 100` for
+                                day 11. Only allowed when running a single day.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["run", ref args @ ..] => run(args),
+        ["verify"] => verify("all"),
+        ["verify", days] => verify(days),
+        ["fetch", day] => fetch(day),
+        ["submit", ref args @ ..] => submit(args),
+        ["examples", ref args @ ..] => examples(args),
+        ["new", day] => new(day),
+        ["gen", ref args @ ..] => gen(args),
+        ["draw", ref args @ ..] => draw(args),
+        ["watch", day] => watch(day, None),
+        ["watch", day, input] => watch(day, Some(input)),
+        ["repl", day] => explore(day, None),
+        ["repl", day, input] => explore(day, Some(input)),
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[&str]) -> ExitCode {
+    let (options, days, inputs) = match Options::parse(args) {
+        Ok((options, rest)) => match rest[..] {
+            [days, ref inputs @ ..] => (options, days, inputs.to_vec()),
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let selected = match select_days(days) {
+        Some(selected) => selected,
+        None => return ExitCode::FAILURE,
+    };
+
+    if selected.len() > 1 {
+        if !inputs.is_empty() {
+            eprintln!("error: input files can only be given when running a single day");
+            return ExitCode::FAILURE;
+        }
+        if options.params.names().next().is_some() {
+            eprintln!("error: parameters can only be given when running a single day");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    for &(year, &(day, _, params)) in &selected {
+        if let Err(err) = options.check_params(params) {
+            eprintln!("error: day {}: {}", label(year, day), err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    // A job per input of the single day, or per day with its default input
+    let jobs: Vec<(Date, Solver, String)> = match (&selected[..], &inputs[..]) {
+        ([(year, (day, solve, _))], [_, ..]) => inputs
+            .iter()
+            .map(|path| ((*year, *day), *solve, path.to_string()))
+            .collect(),
+        _ => selected
+            .iter()
+            .map(|(year, (day, solve, _))| {
+                let path = input_path(*year, *day).display().to_string();
+                ((*year, *day), *solve, path)
+            })
+            .collect(),
+    };
+
+    let start = Instant::now();
+    let results = run_jobs(jobs, &options);
+    let total_time = start.elapsed();
+
+    match options.format {
+        Format::Text if results.len() == 1 => print_text(results[0].0, None, &results[0].2),
+        Format::Text if selected.len() == 1 => {
+            for (day, path, result) in &results {
+                print_text(*day, Some(path), result);
+            }
+        }
+        Format::Text => {
+            for &((year, day), _, ref result) in &results {
+                match result {
+                    Ok(solved) => {
+                        for part in &solved.parts {
+                            if let Err(err) = &part.answer {
+                                eprintln!(
+                                    "error: day {}: part {}: {}",
+                                    label(year, day),
+                                    part.part,
+                                    err
+                                );
+                            }
+                        }
+                    }
+                    Err(err) => eprintln!("error: day {}: {}", label(year, day), err),
+                }
+            }
+            print!("{}", format_table(&results, options.part, total_time));
+        }
+        Format::Json => {
+            let days: Vec<String> = results
+                .iter()
+                .map(|(day, path, result)| day_json(*day, path, result))
+                .collect();
+            println!("{}", json::array(&days));
+        }
+    }
+
+    if results
+        .iter()
+        .all(|(_, _, result)| matches!(result, Ok(solved) if solved.is_complete()))
+    {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// A day solved on the input at a path, or why it couldn't be
+type Run = (Date, String, Result<Solved, String>);
+
+/// Solves every job at once. Inputs that can't be read, errors and panics all end up as the result
+/// of their own job, leaving the others be. Panics still print where they happened on stderr, as
+/// the panic hook is left alone for the rest of the process.
+fn run_jobs(jobs: Vec<(Date, Solver, String)>, options: &Options) -> Vec<Run> {
+    let paths: Vec<(Date, String)> = jobs
+        .iter()
+        .map(|(day, _, path)| (*day, path.clone()))
+        .collect();
+
+    let results = parallel::map(jobs, |(_, solve, path)| solve_input(solve, &path, options));
+
+    paths
+        .into_iter()
+        .zip(results)
+        .map(|((day, path), result)| {
+            (
+                day,
+                path,
+                result.unwrap_or_else(|err| Err(format!("panicked: {}", err))),
+            )
+        })
+        .collect()
+}
+
+/// Prints the answers of a day of a year, naming the input it was run on if `path` is given
+fn print_text((year, day): Date, path: Option<&str>, result: &Result<Solved, String>) {
+    match path {
+        Some(path) => println!("Day {} ({})", label(year, day), path),
+        None => println!("Day {}", label(year, day)),
+    }
+
+    match result {
+        Ok(solved) => {
+            for part in &solved.parts {
+                match &part.answer {
+                    Ok(answer) => println!("{}", format_answer(part.part, answer)),
+                    Err(err) => eprintln!("  error: part {}: {}", part.part, err),
+                }
+            }
+        }
+        Err(err) => eprintln!("  error: {}", err),
+    }
+}
+
+/// Table with a row per day, holding the answers and the time taken to parse and solve it, and
+/// the total time. Text answers don't fit in a cell, so they go after the table.
+fn format_table(results: &[Run], part: Part, total: Duration) -> String {
+    let parts = part.numbers();
+    let mut rows = vec![];
+    let mut texts = vec![];
+
+    let mut header = vec!["Day".to_owned()];
+    header.extend(parts.iter().map(|part| format!("Part {}", part)));
+    header.push("Time".to_owned());
+
+    for &((year, day), _, ref result) in results {
+        let mut row = vec![label(year, day)];
+
+        match result {
+            Ok(solved) => {
+                for part in &solved.parts {
+                    match &part.answer {
+                        Ok(Answer::Number(n)) => row.push(n.to_string()),
+                        Ok(Answer::Text(text)) => {
+                            row.push("(below)".to_owned());
+                            texts.push(format_labeled(
+                                "",
+                                &format!("Day {}, part {}", label(year, day), part.part),
+                                &Answer::Text(text.clone()),
+                            ));
+                        }
+                        Err(_) => row.push("error".to_owned()),
+                    }
+                }
+                let time = solved.parse_time + solved.parts.iter().map(|part| part.time).sum();
+                row.push(format_duration(time));
+            }
+            Err(_) => {
+                row.push("error".to_owned());
+                row.resize(parts.len() + 1, String::new());
+                row.push(String::new());
+            }
+        }
+
+        rows.push(row);
+    }
+
+    let mut total_row = vec!["Total".to_owned()];
+    total_row.resize(parts.len() + 1, String::new());
+    total_row.push(format_duration(total));
+
+    let mut widths = vec![0; header.len()];
+    for row in [&header, &total_row].into_iter().chain(&rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String]| {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        cells.join("  ").trim_end().to_owned() + "\n"
+    };
+
+    let mut table = format_row(&header);
+    for row in &rows {
+        table.push_str(&format_row(row));
+    }
+    table.push_str(&format_row(&total_row));
+
+    for text in texts {
+        table.push('\n');
+        table.push_str(&text);
+        table.push('\n');
+    }
+
+    table
+}
+
+/// Formats a duration with a unit that keeps it readable, like `85.3 µs` or `12.40 ms`
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1e6;
+
+    if micros < 1e3 {
+        format!("{:.1} µs", micros)
+    } else if micros < 1e6 {
+        format!("{:.2} ms", micros / 1e3)
+    } else {
+        format!("{:.2} s", micros / 1e6)
+    }
+}
+
+/// JSON object with the results of a day of a year, or the error that stopped it
+fn day_json((year, day): Date, path: &str, result: &Result<Solved, String>) -> String {
+    let mut fields = vec![
+        ("year", year.to_string()),
+        ("day", day.to_string()),
+        ("input", json::quote(path)),
+    ];
+
+    match result {
+        Ok(solved) => fields.extend(solved.json_fields()),
+        Err(err) => fields.push(("error", json::quote(err))),
+    }
+
+    json::object(&fields)
+}
+
+fn verify(days: &str) -> ExitCode {
+    let selected = match select_days(days) {
+        Some(selected) => selected,
+        None => return ExitCode::FAILURE,
+    };
+
+    let answers: Answers = match read_input_from(ANSWERS) {
+        Ok(manifest) => match manifest.parse() {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprintln!("error: cannot parse {}: {}", ANSWERS, err);
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(err) => {
+            eprintln!("error: cannot read {}: {}", ANSWERS, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = 0;
+
+    for (year, (day, solve, _)) in selected {
+        let path = input_path(year, *day);
+        println!("Day {}", label(year, *day));
+
+        let result = solve_input(*solve, &path.display().to_string(), &Options::default());
+
+        let solved = match result {
+            Ok(solved) => solved,
+            Err(err) => {
+                eprintln!("  error: {}", err);
+                failures += 1;
+                continue;
+            }
+        };
+
+        for SolvedPart { part, answer, .. } in solved.parts {
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(err) => {
+                    eprintln!("  error: part {}: {}", part, err);
+                    failures += 1;
+                    continue;
+                }
+            };
+            match answers.check(year, *day, part, &answer) {
+                Check::Correct => println!("  Part {}: ok", part),
+                Check::Wrong { expected } => {
+                    println!("  Part {}: MISMATCH", part);
+                    println!("{}", format_labeled("    ", "expected", expected));
+                    println!("{}", format_labeled("    ", "got", &answer));
+                    failures += 1;
+                }
+                Check::Unknown => {
+                    println!("  Part {}: no known answer in {}", part, ANSWERS);
+                    println!("{}", format_labeled("    ", "got", &answer));
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("error: {} answers do not match {}", failures, ANSWERS);
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fetch(day: &str) -> ExitCode {
+    let (year, day) = match parse_day(day) {
+        Some(day) => day,
+        None => return ExitCode::FAILURE,
+    };
+
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: cannot load config: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let path = input_path(year, day);
+    match fetch_input(&config, year, day, &path) {
+        Ok(Fetched::Cached) => {
+            println!("{} is already there", path.display());
+            ExitCode::SUCCESS
+        }
+        Ok(Fetched::Downloaded) => {
+            println!(
+                "Saved the input for day {} in {}",
+                label(year, day),
+                path.display()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: cannot fetch day {}: {}", label(year, day), err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(args: &[&str]) -> ExitCode {
+    let parsed = take_option(args, "answer").and_then(|(answer, rest)| {
+        let (options, rest) = Options::parse(&rest)?;
+        Ok((answer, options, rest))
+    });
+    let (answer, options, day, part, input) = match parsed {
+        Ok((answer, options, rest)) => match rest[..] {
+            [day, part] => (answer, options, day, part, None),
+            [day, part, input] => (answer, options, day, part, Some(input)),
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if options.part != Part::default() {
+        eprintln!("error: `aoc submit` takes the part after the day, without `--part`");
+        return ExitCode::FAILURE;
+    }
+    if options.format != Format::default() {
+        eprintln!("error: `aoc submit` prints no answers, so it takes no `--format`");
+        return ExitCode::FAILURE;
+    }
+    if let (Some(_), Some(name)) = (answer, options.params.names().next()) {
+        eprintln!(
+            "error: `--answer` is sent without solving the day, so `--{}` does nothing",
+            name
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let (year, day) = match parse_day(day) {
+        Some(day) => day,
+        None => return ExitCode::FAILURE,
+    };
+    let part = match part {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => {
+            eprintln!("error: invalid part `{}`, expected 1 or 2", part);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let answer = match answer {
+        Some(answer) => answer.to_owned(),
+        None => match solve_part(year, day, part, input, options) {
+            Ok(Answer::Text(text)) if text.trim().contains('\n') => {
+                eprintln!(
+                    "error: the answer is a drawing:\n{}\nSend the letters in it with `--answer`",
+                    text.trim_end()
+                );
+                return ExitCode::FAILURE;
+            }
+            Ok(answer) => answer.to_string().trim().to_owned(),
+            Err(err) => {
+                eprintln!("error: day {}: {}", label(year, day), err);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let part = part.numbers()[0];
+    println!("Day {} part {}: {}", label(year, day), part, answer);
+
+    let client = match Config::load() {
+        Ok(config) => Client::new(&config),
+        Err(err) => {
+            eprintln!("error: cannot load config: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = match client {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("error: cannot submit: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut attempts: Attempts = match fs::read_to_string(ATTEMPTS) {
+        Ok(log) => match log.parse() {
+            Ok(attempts) => attempts,
+            Err(err) => {
+                eprintln!("error: cannot parse {}: {}", ATTEMPTS, err);
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Attempts::default(),
+        Err(err) => {
+            eprintln!("error: cannot read {}: {}", ATTEMPTS, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let outcome = submit::submit(
+        &client,
+        &mut attempts,
+        year,
+        day,
+        part,
+        &answer,
+        &mut Patience,
+    );
+    if let Ok(Outcome::Judged(_)) = outcome {
+        if let Err(err) = fs::write(ATTEMPTS, attempts.to_string()) {
+            eprintln!("error: cannot write {}: {}", ATTEMPTS, err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    match outcome {
+        Ok(Outcome::Judged(Verdict::Right)) => {
+            println!("That's the right answer!");
+            ExitCode::SUCCESS
+        }
+        Ok(Outcome::Judged(verdict)) => {
+            println!("That's not the right answer, it's {}", verdict);
+            ExitCode::FAILURE
+        }
+        Ok(Outcome::Known(known)) => {
+            eprintln!("error: not submitted, {}", known);
+            ExitCode::FAILURE
+        }
+        Ok(Outcome::WrongLevel) => {
+            eprintln!(
+                "error: the website doesn't take answers to this part, is it solved already?"
+            );
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("error: cannot submit: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Solves a single part of a day of `year` on its input, or on the default one
+fn solve_part(
+    year: u16,
+    day: u8,
+    part: Part,
+    input: Option<&str>,
+    options: Options,
+) -> Result<Answer, String> {
+    let (_, solve, params) = days(year)
+        .and_then(|days| days.iter().find(|(n, _, _)| *n == day))
+        .ok_or("not solved yet")?;
+    options.check_params(params)?;
+
+    let path = input.map_or_else(
+        || input_path(year, day).display().to_string(),
+        str::to_owned,
+    );
+    let solved = solve_input(*solve, &path, &Options { part, ..options })?;
+
+    let SolvedPart { part, answer, .. } = solved.parts.into_iter().next().unwrap();
+    answer.map_err(|err| format!("part {}: {}", part, err))
+}
+
+/// The real clock, saying so when it waits for the website
+struct Patience;
+
+impl Clock for Patience {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs())
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        println!("Waiting {}s, as the website asked", duration.as_secs());
+        thread::sleep(duration);
+    }
+}
+
+fn examples(args: &[&str]) -> ExitCode {
+    let (blocks, day, page) = match take_option(args, "blocks") {
+        Ok((blocks, rest)) => match rest[..] {
+            [day, page] => (blocks, day, page),
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (year, day) = match parse_day(day) {
+        Some(day) => day,
+        None => return ExitCode::FAILURE,
+    };
+    let blocks: Option<Vec<usize>> = match blocks.map(|blocks| {
+        blocks
+            .split(',')
+            .map(|block| block.trim().parse())
+            .collect::<Result<_, _>>()
+    }) {
+        Some(Ok(blocks)) => Some(blocks),
+        Some(Err(_)) => {
+            eprintln!("error: `--blocks` takes block numbers like `3,2`");
+            return ExitCode::FAILURE;
+        }
+        None => None,
+    };
+
+    let html = match read_input_from(page) {
+        Ok(html) => html,
+        Err(err) => {
+            eprintln!("error: cannot read {}: {}", page, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut page = parse_page(&html);
+    let solver = days(year).and_then(|days| days.iter().find(|(n, _, _)| *n == day));
+    match (&blocks, solver) {
+        (Some(blocks), _) => {
+            if let Err(err) = page.pick(blocks) {
+                eprintln!("error: {}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+        // Days not solved yet can't tell inputs from diagrams, so they get every block
+        (None, Some(&(_, solve, _))) => page.retain_inputs(|example| parses(solve, example)),
+        (None, None) => {}
+    }
+    if page.examples.is_empty() {
+        eprintln!("error: no examples found in the page");
+        return ExitCode::FAILURE;
+    }
+
+    match write_fixtures(&page, year, day, &fixtures_dir(year, day)) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: cannot write fixtures: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Whether `solve` can parse `example`, which tells the inputs of a puzzle page from its diagrams
+fn parses(solve: Solver, example: &str) -> bool {
+    let options = Options {
+        part: Part::One,
+        ..Options::default()
+    };
+    !matches!(
+        solve(&mut example.as_bytes(), &options),
+        Err(SolveError::Parse(_) | SolveError::Read(_))
+    )
+}
+
+fn watch(day: &str, input: Option<&str>) -> ExitCode {
+    let (year, day) = match parse_day(day) {
+        Some(day) => day,
+        None => return ExitCode::FAILURE,
+    };
+
+    let input = match input {
+        Some(input) => input.to_owned(),
+        None => input_path(year, day).display().to_string(),
+    };
+    let source = Path::new("src")
+        .join(format!("year{}", year))
+        .join(format!("day{:02}.rs", day));
+
+    println!(
+        "Watching {} and {}, press Ctrl-C to stop",
+        source.display(),
+        input
+    );
+
+    let mut watcher = Watcher::new(vec![source, input.clone().into()]);
+    check_day(year, day, &input);
+
+    loop {
+        thread::sleep(WATCH_INTERVAL);
+
+        let changed = watcher.changed();
+        if !changed.is_empty() {
+            let names: Vec<_> = changed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            println!("\n--- {} changed", names.join(", "));
+            check_day(year, day, &input);
+        }
+    }
+}
+
+/// Runs the tests and the solution of a day through cargo, so they are rebuilt from the current
+/// source, and prints a summary of both
+fn check_day(year: u16, day: u8, input: &str) {
+    let filter = format!("year{}::day{:02}::", year, day);
+    let tests = cargo(&["test", "--quiet", "--lib", &filter]);
+
+    match TestSummary::from_output(&tests) {
+        Some(summary) if summary.failed == 0 => println!("Tests: ok ({} passed)", summary.passed),
+        Some(summary) => {
+            println!(
+                "Tests: FAILED ({} of {} failed)",
+                summary.failed,
+                summary.passed + summary.failed
+            );
+            for failure in summary.failures {
+                println!("  {}", failure);
+            }
+        }
+        None => {
+            println!("Tests: build failed");
+            for line in tests.lines().filter(|line| line.starts_with("error")) {
+                println!("  {}", line);
+            }
+            return;
+        }
+    }
+
+    print!(
+        "{}",
+        cargo(&["run", "--quiet", "--", "run", &label(year, day), input])
+    );
+}
+
+/// Runs cargo with `args` and returns everything it printed
+fn cargo(args: &[&str]) -> String {
+    match Command::new(env!("CARGO")).args(args).output() {
+        Ok(Output { stdout, stderr, .. }) => {
+            String::from_utf8_lossy(&stdout).into_owned() + &String::from_utf8_lossy(&stderr)
+        }
+        Err(err) => format!("error: cannot run cargo: {}\n", err),
+    }
+}
+
+fn new(day: &str) -> ExitCode {
+    let (year, day) = match parse_day(day) {
+        Some(day) => day,
+        None => return ExitCode::FAILURE,
+    };
+
+    match scaffold(Path::new("."), year, day) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: cannot create day {}: {}", label(year, day), err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn gen(args: &[&str]) -> ExitCode {
+    let (options, day) = match Options::parse(args) {
+        Ok((options, rest)) if rest.len() == 1 => (options, rest[0]),
+        Ok(_) => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (year, day) = match parse_day(day) {
+        Some(day) => day,
+        None => return ExitCode::FAILURE,
+    };
+    if let Err(err) = options.check_params(&["size", "seed"]) {
+        eprintln!("error: {}", err);
+        return ExitCode::FAILURE;
+    }
+
+    let (generator, default_size) = match generator(year, day) {
+        Some(generator) => generator,
+        None => {
+            eprintln!("error: there is no generator for day {}", label(year, day));
+            return ExitCode::FAILURE;
+        }
+    };
+    let size = match options.params.get("size") {
+        // An input with nothing in it is not one the puzzle would give
+        Some(0) => {
+            eprintln!("error: the size must be at least 1");
+            return ExitCode::FAILURE;
+        }
+        Some(size) => size as usize,
+        None => default_size,
+    };
+    let mut rng = Rng::new(options.params.get("seed").unwrap_or(0));
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    match generator(&mut rng, size, &mut out).and_then(|_| out.flush()) {
+        Ok(()) => ExitCode::SUCCESS,
+        // Whoever reads the input stopped early, like `head`
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: cannot write the input: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn draw(args: &[&str]) -> ExitCode {
+    let (animate, args) = take_flag(args, "animate");
+    let parsed = take_option(&args, "out").and_then(|(out, rest)| {
+        let (colours, rest) = take_option(&rest, "colours")?;
+        let (options, rest) = Options::parse(&rest)?;
+        Ok((out, colours, options, rest))
+    });
+    let (out, colours, options, day, input) = match parsed {
+        Ok((out, colours, options, rest)) if !(animate && out.is_some()) => match rest[..] {
+            [day] => (out, colours, options, day, None),
+            [day, input] => (out, colours, options, day, Some(input)),
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            }
+        },
+        Ok(_) => {
+            eprintln!("error: `--animate` draws in the terminal, not to `--out`");
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (year, day) = match parse_day(day) {
+        Some(day) => day,
+        None => return ExitCode::FAILURE,
+    };
+    let (record, params, palette) = match visual(year, day) {
+        Some(visual) => visual,
+        None => {
+            eprintln!("error: day {} cannot be drawn", label(year, day));
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(err) = options.check_params(&[params, &["scale", "delay"]].concat()) {
+        eprintln!("error: {}", err);
+        return ExitCode::FAILURE;
+    }
+    if animate && options.params.get("scale").is_some() {
+        eprintln!("error: `--animate` draws in the terminal, without a `--scale`");
+        return ExitCode::FAILURE;
+    }
+
+    let palette = match colours.map(str::parse::<Palette>) {
+        Some(Ok(stops)) => Palette::gradient(stops.colours(), palette.colours().len()),
+        Some(Err(err)) => {
+            eprintln!("error: invalid colours: {}", err);
+            return ExitCode::FAILURE;
+        }
+        None => palette,
+    };
+
+    let path = input.map_or_else(
+        || input_path(year, day).display().to_string(),
+        str::to_owned,
+    );
+    let frames = match read_input_from(&path) {
+        Ok(input) => match record(&input, &options.params) {
+            Ok(frames) => frames,
+            Err(DrawError::Parse(err)) => {
+                eprintln!("error: cannot parse {}: {}", path, err);
+                return ExitCode::FAILURE;
+            }
+            Err(err) => {
+                eprintln!("error: cannot draw {}: {}", path, err);
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(err) => {
+            eprintln!("error: cannot read {}: {}", path, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let delay = Duration::from_millis(options.params.get("delay").unwrap_or(100));
+    if animate {
+        return match play(&frames, &palette, delay) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: cannot animate: {}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let scale = options
+        .params
+        .get("scale")
+        .map_or_else(
+            || default_scale(&frames),
+            |scale| usize::try_from(scale).unwrap_or(usize::MAX),
+        )
+        .max(1);
+    let width = frames.iter().map(Frame::width).max().unwrap_or(0);
+    let height = frames.iter().map(Frame::height).max().unwrap_or(0);
+    if let Err(err) = check_scale(width, height, scale) {
+        eprintln!("error: cannot draw {}: {}", path, err);
+        return ExitCode::FAILURE;
+    }
+    let out = out.map_or_else(|| format!("{}-day{:02}.gif", year, day), str::to_owned);
+
+    let written = if out.ends_with(".gif") {
+        File::create(&out).and_then(|file| {
+            let mut file = BufWriter::new(file);
+            write_gif(&mut file, &frames, &palette, scale, delay)?;
+            file.flush()
+        })
+    } else {
+        write_pngs(Path::new(&out), &frames, &palette, scale).map(|_| ())
+    };
+
+    match written {
+        Ok(()) => {
+            println!("Wrote {} frames to {}", frames.len(), out);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: cannot write {}: {}", out, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Reads commands from stdin and runs them on the parsed input of a day, until `quit` or the end
+/// of the input
+fn explore(day: &str, input: Option<&str>) -> ExitCode {
+    let (year, day) = match parse_day(day) {
+        Some(day) => day,
+        None => return ExitCode::FAILURE,
+    };
+    let start = match repl(year, day) {
+        Some(start) => start,
+        None => {
+            eprintln!("error: day {} cannot be explored", label(year, day));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let path = input.map_or_else(
+        || input_path(year, day).display().to_string(),
+        str::to_owned,
+    );
+    let mut session = match read_input_from(&path) {
+        Ok(input) => match start(&input) {
+            Ok(session) => session,
+            Err(err) => {
+                eprintln!("error: cannot parse {}: {}", path, err);
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(err) => {
+            eprintln!("error: cannot read {}: {}", path, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("Parsed {}, `help` lists the commands", path);
+    let stdin = io::stdin();
+    let mut line = String::new();
+    loop {
+        print!("{}> ", label(year, day));
+        if io::stdout().flush().is_err() {
+            return ExitCode::FAILURE;
+        }
+
+        line.clear();
+        match stdin.read_line(&mut line) {
+            // Leave the prompt on a line of its own
+            Ok(0) => {
+                println!();
+                return ExitCode::SUCCESS;
+            }
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("error: cannot read a command: {}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+
+        match session.eval(&line) {
+            Reply::Output(output) if output.is_empty() => {}
+            Reply::Output(output) => println!("{}", output),
+            Reply::Error(err) => eprintln!("error: {}", err),
+            Reply::Quit => return ExitCode::SUCCESS,
+        }
+    }
+}
+
+/// Solves a day on the input at `path`, parsing it as it is read
+fn solve_input(solve: Solver, path: &str, options: &Options) -> Result<Solved, String> {
+    let mut reader = Source::new(path)
+        .open()
+        .map_err(|err| format!("cannot read {}: {}", path, err))?;
+    solve(&mut reader, options).map_err(|err| describe(path, err))
+}
+
+/// Describes why a day could not be solved on the input at `path`
+fn describe(path: &str, err: SolveError) -> String {
+    match err {
+        SolveError::Read(err) => format!("cannot read {}: {}", path, err),
+        SolveError::Parse(err) => format!("cannot parse {}: {}", path, err),
+    }
+}
+
+/// Pixels per cell that make the biggest frame about 512 pixels wide or high
+fn default_scale(frames: &[Frame]) -> usize {
+    let side = frames
+        .iter()
+        .map(|frame| frame.width().max(frame.height()))
+        .max()
+        .unwrap_or(0);
+    512 / side.max(1)
+}
+
+/// Parses a single day like `2021/5`, or `5` for a day of the latest year. Errors are reported
+/// on stderr.
+fn parse_day(day: &str) -> Option<Date> {
+    let (year, number) = match day.split_once('/') {
+        Some((year, number)) => (year.parse().ok(), number),
+        None => (Some(latest_year()), day),
+    };
+
+    match (year, number.parse::<u8>()) {
+        (Some(year), Ok(number)) if CALENDARS.contains(&year) && (1..=25).contains(&number) => {
+            Some((year, number))
+        }
+        _ => {
+            eprintln!("error: invalid day `{}`\n\n{}", day, USAGE);
+            None
+        }
+    }
+}
+
+/// Picks the registered days matching a selection like `5`, `3..9`, `all`, or the same after a
+/// year like `2021/all`. Errors are reported on stderr.
+fn select_days(days: &str) -> Option<Vec<(u16, &'static Day)>> {
+    let (years, range) = match parse_days(days) {
+        Some(selection) => selection,
+        None => {
+            eprintln!("error: invalid day selection `{}`\n\n{}", days, USAGE);
+            return None;
+        }
+    };
+
+    let selected: Vec<_> = YEARS
+        .iter()
+        .filter(|(year, _)| years.contains(year))
+        .flat_map(|&(year, listed)| listed.days.iter().map(move |day| (year, day)))
+        .filter(|(_, (n, _, _))| range.contains(n))
+        .collect();
+
+    if selected.is_empty() {
+        eprintln!("error: no registered days in {}", days);
+        return None;
+    }
+
+    Some(selected)
+}
+
+/// Parses `all`, `N` or `A..B` (inclusive), optionally after a year like `2021/`, into a range of
+/// years and a range of days. Without a year, `all` is every day of every year and the others
+/// are days of the latest year.
+fn parse_days(days: &str) -> Option<(RangeInclusive<u16>, RangeInclusive<u8>)> {
+    let (years, days) = match days.split_once('/') {
+        Some((year, days)) => {
+            let year = year.parse().ok()?;
+            (year..=year, days)
+        }
+        None if days == "all" => (CALENDARS, days),
+        None => (latest_year()..=latest_year(), days),
+    };
+
+    if days == "all" {
+        return Some((years, 1..=25));
+    }
+
+    if let Some((start, end)) = days.split_once("..") {
+        let start = start.parse().ok()?;
+        let end = end.trim_start_matches('=').parse().ok()?;
+        return Some((years, start..=end));
+    }
+
+    let day = days.parse().ok()?;
+    Some((years, day..=day))
+}
+
+/// Labels a day of a year, like `2021/05`
+fn label(year: u16, day: u8) -> String {
+    format!("{}/{:02}", year, day)
+}
+
+/// Formats the answer to a part
+fn format_answer(part: u8, answer: &Answer) -> String {
+    format_labeled("  ", &format!("Part {}", part), answer)
+}
+
+/// Formats an answer after a label. Text answers go on their own lines, indented under the label.
+fn format_labeled(indent: &str, label: &str, answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => format!("{}{}: {}", indent, label, n),
+        Answer::Text(text) => {
+            let lines: Vec<String> = text
+                .lines()
+                .map(|line| format!("{}  {}", indent, line))
+                .collect();
+            format!("{}{}:\n{}", indent, label, lines.join("\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_day_selections() {
+        let latest = latest_year()..=latest_year();
+
+        assert_eq!(parse_days("all"), Some((CALENDARS, 1..=25)));
+        assert_eq!(parse_days("5"), Some((latest.clone(), 5..=5)));
+        assert_eq!(parse_days("3..9"), Some((latest.clone(), 3..=9)));
+        assert_eq!(parse_days("3..=9"), Some((latest, 3..=9)));
+        assert_eq!(parse_days("2021/all"), Some((2021..=2021, 1..=25)));
+        assert_eq!(parse_days("2020/3..9"), Some((2020..=2020, 3..=9)));
+        assert_eq!(parse_days("foo"), None);
+        assert_eq!(parse_days("3.."), None);
+        assert_eq!(parse_days("21x/5"), None);
+    }
+
+    #[test]
+    fn it_parses_single_days() {
+        assert_eq!(parse_day("2021/5"), Some((2021, 5)));
+        assert_eq!(parse_day("7"), Some((latest_year(), 7)));
+        assert_eq!(parse_day("2014/5"), None);
+        assert_eq!(parse_day("2021/26"), None);
+    }
+
+    #[test]
+    fn it_formats_answers() {
+        assert_eq!(format_answer(1, &Answer::Number(42)), "  Part 1: 42");
+        assert_eq!(
+            format_answer(2, &Answer::Text("#.\n.#\n".to_owned())),
+            "  Part 2:\n    #.\n    .#"
+        );
+    }
+
+    #[test]
+    fn it_runs_every_day_of_a_batch_even_if_one_fails() {
+        let solver = |day| {
+            days(2021)
+                .unwrap()
+                .iter()
+                .find(|(n, _, _)| *n == day)
+                .unwrap()
+                .1
+        };
+        let octopuses = env::temp_dir().join(format!("aoc-run-{}.txt", std::process::id()));
+        // Found by `aoc gen 11 --size 2 --seed 3`, the octopuses never all flash at once
+        fs::write(&octopuses, "17\n60\n").unwrap();
+
+        let jobs = vec![
+            (
+                (2021, 1),
+                solver(1),
+                "fixtures/2021/day01/example1.txt".to_owned(),
+            ),
+            ((2021, 11), solver(11), octopuses.display().to_string()),
+            (
+                (2021, 13),
+                solver(13),
+                "fixtures/2021/day13/example1.txt".to_owned(),
+            ),
+        ];
+        let results = run_jobs(jobs, &Options::default());
+        fs::remove_file(&octopuses).unwrap();
+
+        let days: Vec<Date> = results.iter().map(|(day, _, _)| *day).collect();
+        assert_eq!(days, [(2021, 1), (2021, 11), (2021, 13)]);
+        assert!(results[0].2.is_ok());
+        assert!(results[2].2.is_ok());
+
+        // Part 2 failing leaves part 1's answer be
+        let octopuses = results[1].2.as_ref().unwrap();
+        assert!(octopuses.parts[0].answer.is_ok());
+        assert_eq!(
+            octopuses.parts[1].answer,
+            Err("the octopuses never all flash at once".to_owned())
+        );
+        assert!(!octopuses.is_complete());
+    }
+
+    #[test]
+    fn it_formats_the_summary_table() {
+        let solved = Solved {
+            parse_time: Duration::from_micros(20),
+            parts: vec![
+                SolvedPart {
+                    part: 1,
+                    answer: Ok(Answer::Number(743)),
+                    time: Duration::from_micros(30),
+                },
+                SolvedPart {
+                    part: 2,
+                    answer: Ok(Answer::Text("#.\n.#".to_owned())),
+                    time: Duration::from_millis(2),
+                },
+            ],
+        };
+        let synced = Solved {
+            parse_time: Duration::from_micros(10),
+            parts: vec![
+                SolvedPart {
+                    part: 1,
+                    answer: Ok(Answer::Number(1656)),
+                    time: Duration::from_micros(40),
+                },
+                SolvedPart {
+                    part: 2,
+                    answer: Err("the octopuses never all flash at once".to_owned()),
+                    time: Duration::from_micros(50),
+                },
+            ],
+        };
+        let results = vec![
+            ((2021, 10), "-".to_owned(), Err("panicked: oops".to_owned())),
+            ((2021, 11), "-".to_owned(), Ok(synced)),
+            ((2021, 13), "inputs/2021/day13.txt".to_owned(), Ok(solved)),
+        ];
+
+        assert_eq!(
+            format_table(&results, Part::Both, Duration::from_millis(3)),
+            "Day      Part 1  Part 2   Time
+2021/10  error
+2021/11  1656    error    100.0 µs
+2021/13  743     (below)  2.05 ms
+Total                     3.00 ms
+
+Day 2021/13, part 2:
+  #.
+  .#
+"
+        );
+    }
+
+    #[test]
+    fn it_formats_days_as_json() {
+        let solved = Solved {
+            parse_time: Duration::from_nanos(1500),
+            parts: vec![SolvedPart {
+                part: 2,
+                answer: Ok(Answer::Text("#.\n.#".to_owned())),
+                time: Duration::from_micros(2),
+            }],
+        };
+        let synced = Solved {
+            parse_time: Duration::from_nanos(1000),
+            parts: vec![
+                SolvedPart {
+                    part: 1,
+                    answer: Ok(Answer::Number(1656)),
+                    time: Duration::from_nanos(3000),
+                },
+                SolvedPart {
+                    part: 2,
+                    answer: Err("the octopuses never all flash at once".to_owned()),
+                    time: Duration::from_nanos(4000),
+                },
+            ],
+        };
+
+        assert_eq!(
+            day_json((2021, 13), "inputs/2021/day13.txt", &Ok(solved)),
+            r##"{"year": 2021, "day": 13, "input": "inputs/2021/day13.txt", "parse_time_ns": 1500, "parts": [{"part": 2, "type": "text", "answer": "#.\n.#", "time_ns": 2000}]}"##
+        );
+        assert_eq!(
+            day_json((2021, 11), "-", &Ok(synced)),
+            r#"{"year": 2021, "day": 11, "input": "-", "parse_time_ns": 1000, "parts": [{"part": 1, "type": "number", "answer": 1656, "time_ns": 3000}, {"part": 2, "error": "the octopuses never all flash at once", "time_ns": 4000}]}"#
+        );
+        assert_eq!(
+            day_json((2021, 6), "-", &Err("cannot read -".to_owned())),
+            r#"{"year": 2021, "day": 6, "input": "-", "error": "cannot read -"}"#
+        );
+    }
 }
@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Which parts of a puzzle to solve
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl Part {
+    /// The part numbers this selection covers
+    pub fn numbers(self) -> &'static [u8] {
+        match self {
+            Part::One => &[1],
+            Part::Two => &[2],
+            Part::Both => &[1, 2],
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(part: &str) -> Result<Self, String> {
+        match part {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "both" => Ok(Part::Both),
+            _ => Err(format!("invalid part `{}`, expected 1, 2 or both", part)),
+        }
+    }
+}
+
+/// How to print the results
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    /// JSON objects with the type of each answer and timings
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, String> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "invalid format `{}`, expected text or json",
+                format
+            )),
+        }
+    }
+}
+
+/// Named puzzle parameters given in the command line, like `--days 80`. Parts fall back to the
+/// values of the puzzle statement for the ones not given.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, u64>,
+}
+
+impl Params {
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.values.get(name).copied()
+    }
+
+    pub fn set(&mut self, name: &str, value: u64) {
+        self.values.insert(name.to_owned(), value);
+    }
+
+    /// Names of the parameters given
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+}
+
+/// Part selection, puzzle parameters and output format for a run
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    pub part: Part,
+    pub params: Params,
+    pub format: Format,
+}
+
+impl Options {
+    /// Takes `--part 1|2|both`, `--format text|json` and every other `--name value` (or
+    /// `--name=value`) pair out of `args`, and returns them along with the remaining arguments
+    pub fn parse<'a>(args: &[&'a str]) -> Result<(Options, Vec<&'a str>), String> {
+        let mut options = Options::default();
+        let mut rest = vec![];
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let name = match arg.strip_prefix("--") {
+                Some(name) if !name.is_empty() => name,
+                _ => {
+                    rest.push(*arg);
+                    continue;
+                }
+            };
+
+            let (name, value) = match name.split_once('=') {
+                Some((name, value)) => (name, value),
+                None => match args.next() {
+                    Some(value) => (name, *value),
+                    None => return Err(format!("missing value for `--{}`", name)),
+                },
+            };
+
+            if name == "part" {
+                options.part = value.parse()?;
+            } else if name == "format" {
+                options.format = value.parse()?;
+            } else {
+                let value = value
+                    .parse()
+                    .map_err(|_| format!("invalid value `{}` for `--{}`", value, name))?;
+                options.params.set(name, value);
+            }
+        }
+
+        Ok((options, rest))
+    }
+
+    /// Checks that every parameter given is one of `known`
+    pub fn check_params(&self, known: &[&str]) -> Result<(), String> {
+        match self.params.names().find(|name| !known.contains(name)) {
+            Some(name) if known.is_empty() => Err(format!(
+                "unknown parameter `--{}`, this day takes none",
+                name
+            )),
+            Some(name) => Err(format!(
+                "unknown parameter `--{}`, expected one of --{}",
+                name,
+                known.join(", --")
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Takes the value of `--name value` (or `--name=value`) out of `args`, for the options that are
+/// not numbers, and returns it along with the remaining arguments
+pub fn take_option<'a>(
+    args: &[&'a str],
+    name: &str,
+) -> Result<(Option<&'a str>, Vec<&'a str>), String> {
+    let flag = format!("--{}", name);
+    let mut value = None;
+    let mut rest = vec![];
+    let mut args = args.iter();
+
+    while let Some(&arg) = args.next() {
+        if arg == flag {
+            match args.next() {
+                Some(&next) => value = Some(next),
+                None => return Err(format!("missing value for `{}`", flag)),
+            }
+        } else if let Some(inline) = arg
+            .strip_prefix(&flag)
+            .and_then(|arg| arg.strip_prefix('='))
+        {
+            value = Some(inline);
+        } else {
+            rest.push(arg);
+        }
+    }
+
+    Ok((value, rest))
+}
+
+/// Takes every `--name` flag out of `args`, and returns whether there was any along with the
+/// remaining arguments
+pub fn take_flag<'a>(args: &[&'a str], name: &str) -> (bool, Vec<&'a str>) {
+    let flag = format!("--{}", name);
+    let rest: Vec<&str> = args.iter().copied().filter(|&arg| arg != flag).collect();
+    (rest.len() < args.len(), rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_options() {
+        let (options, rest) = Options::parse(&[
+            "6",
+            "--part",
+            "1",
+            "input.txt",
+            "--days=18",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+
+        assert_eq!(options.part, Part::One);
+        assert_eq!(options.format, Format::Json);
+        assert_eq!(options.params.get("days"), Some(18));
+        assert_eq!(options.params.get("steps"), None);
+        assert_eq!(rest, vec!["6", "input.txt"]);
+
+        let (options, rest) = Options::parse(&["-"]).unwrap();
+        assert_eq!(options, Options::default());
+        assert_eq!(rest, vec!["-"]);
+    }
+
+    #[test]
+    fn it_fails_to_parse_bad_options() {
+        assert!(Options::parse(&["--part", "3"]).is_err());
+        assert!(Options::parse(&["--format", "xml"]).is_err());
+        assert!(Options::parse(&["--days", "many"]).is_err());
+        assert!(Options::parse(&["--steps"]).is_err());
+    }
+
+    #[test]
+    fn it_takes_options_that_are_not_numbers() {
+        let args = [
+            "11",
+            "--out",
+            "octo.gif",
+            "--steps",
+            "20",
+            "--colours=000000,ffffff",
+        ];
+
+        let (out, rest) = take_option(&args, "out").unwrap();
+        assert_eq!(out, Some("octo.gif"));
+        let (colours, rest) = take_option(&rest, "colours").unwrap();
+        assert_eq!(colours, Some("000000,ffffff"));
+        assert_eq!(rest, vec!["11", "--steps", "20"]);
+
+        assert_eq!(take_option(&rest, "out"), Ok((None, rest.clone())));
+        assert!(take_option(&["--out"], "out").is_err());
+
+        assert_eq!(take_flag(&rest, "animate"), (false, rest.clone()));
+        assert_eq!(
+            take_flag(&["--animate", "11"], "animate"),
+            (true, vec!["11"])
+        );
+    }
+
+    #[test]
+    fn it_checks_params() {
+        let (options, _) = Options::parse(&["--days", "18"]).unwrap();
+
+        assert_eq!(options.check_params(&["days"]), Ok(()));
+        assert!(options.check_params(&["steps"]).is_err());
+        assert!(options.check_params(&[]).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+
+/// Runs `job` on every item of `items` on a pool of worker threads, one per available core, and
+/// returns the results in the order of `items`. A job that panics doesn't take the others down:
+/// its result is the panic message instead.
+pub fn map<T, R, F>(items: Vec<T>, job: F) -> Vec<Result<R, String>>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let count = items.len();
+    let workers = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(count);
+
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (queue, job) = (&queue, &job);
+
+            scope.spawn(move || loop {
+                // The lock is only held to take the next item, and never while running a job
+                let next = queue.lock().unwrap().next();
+                let (index, item) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+
+                let result = panic::catch_unwind(AssertUnwindSafe(|| job(item)));
+                sender.send((index, result.map_err(panic_message))).unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<_> = receiver.into_iter().collect();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// The message a panic was raised with
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_owned(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_keeps_the_order_of_the_items() {
+        let results = map((1..=20).collect(), |n: u64| n * n);
+        let expected: Vec<Result<u64, String>> = (1..=20).map(|n| Ok(n * n)).collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn it_survives_panicking_jobs() {
+        let results = map(vec![1, 0, 2], |n: u32| {
+            if n == 0 {
+                panic!("cannot divide {} by zero", 10);
+            }
+            10 / n
+        });
+
+        assert_eq!(
+            results,
+            vec![Ok(10), Err("cannot divide 10 by zero".to_owned()), Ok(5)]
+        );
+    }
+}
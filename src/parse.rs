@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An error found while parsing a puzzle input. Lines and columns are 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Creates an error in the first line of whatever is being parsed
+    pub fn new<T: Into<String>>(column: usize, expected: T) -> Self {
+        ParseError {
+            line: 1,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error pointing at `part`, which has to be a slice of `line`
+    pub fn at<T: Into<String>>(line: &str, part: &str, expected: T) -> Self {
+        ParseError::new(column_of(line, part), expected)
+    }
+
+    /// Moves an error down by `lines`. Useful when the parser that found it only saw the input
+    /// from a later line onwards.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Re-anchors an error found while parsing `part` so its column points into `line`, of which
+    /// `part` is a slice
+    pub fn within(mut self, line: &str, part: &str) -> Self {
+        self.column += column_of(line, part) - 1;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `part` (a slice of `line`) as a number
+pub fn parse_number<T: FromStr>(line: &str, part: &str) -> Result<T, ParseError> {
+    part.parse()
+        .map_err(|_| ParseError::at(line, part, "a number"))
+}
+
+/// Parses every line in `input` with `parse`, setting the line number of any error
+pub fn parse_lines<T, F>(input: &str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(n, line)| parse(line).map_err(|err| err.offset_lines(n)))
+        .collect()
+}
+
+/// 1-based column where `part` starts inside `line`. Falls back to the first column if `part` is
+/// not actually a slice of `line`.
+fn column_of(line: &str, part: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let part_start = part.as_ptr() as usize;
+
+    if part_start >= start && part_start <= start + line.len() {
+        line[..part_start - start].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_points_at_a_slice_of_the_line() {
+        let line = "forward five";
+        let (_, amount) = line.split_once(' ').unwrap();
+
+        let err = ParseError::at(line, amount, "a number");
+        assert_eq!(err, ParseError::new(9, "a number"));
+        assert_eq!(err.to_string(), "line 1, column 9: expected a number");
+    }
+
+    #[test]
+    fn it_sets_line_numbers() {
+        let err =
+            parse_lines("1\n2\nthree\n4", |line| parse_number::<u32>(line, line)).unwrap_err();
+        assert_eq!(err, ParseError::new(1, "a number").offset_lines(2));
+        assert_eq!(err.line, 3);
+    }
+
+    #[test]
+    fn it_re_anchors_errors_from_a_part_of_the_line() {
+        let line = "0,9 -> 5,x";
+        let (_, end) = line.split_once(" -> ").unwrap();
+        let err = ParseError::at(end, &end[2..], "a number").within(line, end);
+
+        assert_eq!(err.column, 10);
+    }
+}
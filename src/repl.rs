@@ -0,0 +1,325 @@
+use std::str::FromStr;
+
+use crate::geom::Point;
+use crate::solution::find_year;
+use crate::{ParseError, Solution};
+
+/// A command of a day in `aoc repl`: its name, its arguments and what it does
+pub type Command = (&'static str, &'static str, &'static str);
+
+/// Days whose parsed input can be explored one command at a time
+pub trait Explore: Solution {
+    /// What the commands work on, made afresh from the parsed input on `reset`
+    type State;
+
+    /// Every command the day understands, besides the ones every day has
+    const COMMANDS: &'static [Command];
+
+    fn start(input: &Self::Input) -> Self::State;
+
+    /// Runs the command `name` with `args`, returning what to print or what went wrong
+    fn run(state: &mut Self::State, name: &str, args: &[&str]) -> Result<String, String>;
+}
+
+/// Commands every day has
+const BUILTIN: &[Command] = &[
+    ("help", "", "List the commands"),
+    ("reset", "", "Go back to the input as it was parsed"),
+    ("history", "", "List the commands run so far"),
+    ("!!", "", "Run the last command again"),
+    ("!", "<n>", "Run command number n of the history again"),
+    ("quit", "", "Leave, also with `exit` or Ctrl-D"),
+];
+
+/// What to do after a line is read
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reply {
+    Output(String),
+    Error(String),
+    Quit,
+}
+
+/// Reads lines and replies to them
+pub trait Repl {
+    fn eval(&mut self, line: &str) -> Reply;
+}
+
+/// The parsed input of a day, what the commands did to it so far, and the commands run
+pub struct Session<S: Explore> {
+    input: S::Input,
+    state: S::State,
+    history: Vec<String>,
+}
+
+impl<S: Explore> Session<S> {
+    pub fn new(input: S::Input) -> Session<S> {
+        let state = S::start(&input);
+        Session {
+            input,
+            state,
+            history: vec![],
+        }
+    }
+
+    /// The command a line stands for, looking `!!` and `!n` up in the history
+    fn expand(&self, line: &str) -> Result<String, String> {
+        let index = match line.strip_prefix('!') {
+            Some("!") => self.history.len(),
+            Some(n) => n
+                .parse()
+                .map_err(|_| format!("invalid history number `{}`", n))?,
+            None => return Ok(line.to_owned()),
+        };
+
+        match index.checked_sub(1).and_then(|i| self.history.get(i)) {
+            Some(command) => Ok(command.clone()),
+            None => Err(format!("no command {} in the history", index)),
+        }
+    }
+
+    fn help() -> String {
+        let commands: Vec<&Command> = S::COMMANDS.iter().chain(BUILTIN).collect();
+        let usage = |(name, args, _): &Command| match (*name, *args) {
+            (name, "") => name.to_owned(),
+            ("!", args) => format!("!{}", args),
+            (name, args) => format!("{} {}", name, args),
+        };
+        let width = commands.iter().map(|command| usage(command).len()).max();
+
+        commands
+            .iter()
+            .map(|command| {
+                format!(
+                    "{:<width$}  {}",
+                    usage(command),
+                    command.2,
+                    width = width.unwrap_or(0)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<S: Explore> Repl for Session<S> {
+    fn eval(&mut self, line: &str) -> Reply {
+        let line = line.trim();
+        if line.is_empty() {
+            return Reply::Output(String::new());
+        }
+
+        let command = match self.expand(line) {
+            Ok(command) => command,
+            Err(err) => return Reply::Error(err),
+        };
+        self.history.push(command.clone());
+
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let reply = match (words[0], &words[1..]) {
+            ("help", _) => Reply::Output(Self::help()),
+            ("quit" | "exit", _) => Reply::Quit,
+            ("reset", _) => {
+                self.state = S::start(&self.input);
+                Reply::Output("Back to the parsed input".to_owned())
+            }
+            ("history", _) => Reply::Output(
+                (self.history.iter().enumerate())
+                    .map(|(i, command)| format!("{:>4}  {}", i + 1, command))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            (name, args) if S::COMMANDS.iter().any(|(known, _, _)| *known == name) => {
+                match S::run(&mut self.state, name, args) {
+                    Ok(output) => Reply::Output(output),
+                    Err(err) => Reply::Error(err),
+                }
+            }
+            (name, _) => Reply::Error(format!("unknown command `{}`, try `help`", name)),
+        };
+
+        // Show what a command from the history stood for
+        match reply {
+            Reply::Output(output) if command != line => {
+                Reply::Output(format!("{}\n{}", command, output).trim_end().to_owned())
+            }
+            reply => reply,
+        }
+    }
+}
+
+/// Parses `input` for the day and starts a session on it
+pub fn session<S: Explore + 'static>(input: &str) -> Result<Box<dyn Repl>, ParseError> {
+    Ok(Box::new(Session::<S>::new(S::parse(input)?)))
+}
+
+/// Starts a session of a day from its raw input
+pub type Starter = fn(&str) -> Result<Box<dyn Repl>, ParseError>;
+
+/// A day that can be explored, and how to start a session of it
+pub type Entry = (u8, Starter);
+
+/// How to start a session of a day of `year`
+pub fn repl(year: u16, day: u8) -> Option<Starter> {
+    find_year(year)?
+        .repls
+        .iter()
+        .find(|(n, _)| *n == day)
+        .map(|&(_, starter)| starter)
+}
+
+/// The argument at `index` of a command, or `default` if it isn't given
+pub fn arg<T: FromStr>(
+    args: &[&str],
+    index: usize,
+    default: Option<T>,
+    expected: &str,
+) -> Result<T, String> {
+    match (args.get(index), default) {
+        (Some(arg), _) => arg
+            .parse()
+            .map_err(|_| format!("invalid argument `{}`, expected {}", arg, expected)),
+        (None, Some(default)) => Ok(default),
+        (None, None) => Err(format!("missing argument, expected {}", expected)),
+    }
+}
+
+/// The argument at `index` of a command, as a point like `3,4`
+pub fn point_arg(args: &[&str], index: usize) -> Result<Point, String> {
+    let arg = args
+        .get(index)
+        .ok_or("missing argument, expected a point like `3,4`")?;
+
+    match arg.split_once(',').map(|(x, y)| (x.parse(), y.parse())) {
+        Some((Ok(x), Ok(y))) => Ok(Point::new(x, y)),
+        _ => Err(format!(
+            "invalid argument `{}`, expected a point like `3,4`",
+            arg
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::YEARS;
+    use crate::Params;
+    use crate::{Answer, ParseError};
+
+    /// A counter to try the session on
+    struct Counter;
+
+    impl Solution for Counter {
+        type Input = u32;
+
+        fn parse(input: &str) -> Result<u32, ParseError> {
+            crate::parse::parse_number(input, input)
+        }
+
+        fn part1(_: &u32, _: &Params) -> Result<Answer, String> {
+            Ok(Answer::Number(0))
+        }
+
+        fn part2(_: &u32, _: &Params) -> Result<Answer, String> {
+            Ok(Answer::Number(0))
+        }
+    }
+
+    impl Explore for Counter {
+        type State = u32;
+
+        const COMMANDS: &'static [Command] = &[("add", "[n]", "Add n, 1 by default")];
+
+        fn start(input: &u32) -> u32 {
+            *input
+        }
+
+        fn run(count: &mut u32, _name: &str, args: &[&str]) -> Result<String, String> {
+            *count += arg(args, 0, Some(1), "a number")?;
+            Ok(count.to_string())
+        }
+    }
+
+    fn output(text: &str) -> Reply {
+        Reply::Output(text.to_owned())
+    }
+
+    #[test]
+    fn it_runs_the_commands_of_the_day() {
+        let mut session = Session::<Counter>::new(10);
+
+        assert_eq!(session.eval("add"), output("11"));
+        assert_eq!(session.eval("  add 5 "), output("16"));
+        assert_eq!(
+            session.eval("add x"),
+            Reply::Error("invalid argument `x`, expected a number".to_owned())
+        );
+        assert_eq!(session.eval("reset"), output("Back to the parsed input"));
+        assert_eq!(session.eval("add"), output("11"));
+        assert_eq!(
+            session.eval("mul 2"),
+            Reply::Error("unknown command `mul`, try `help`".to_owned())
+        );
+        assert_eq!(session.eval(""), output(""));
+        assert_eq!(session.eval("exit"), Reply::Quit);
+    }
+
+    #[test]
+    fn it_keeps_a_history() {
+        let mut session = Session::<Counter>::new(0);
+        session.eval("add 2");
+        session.eval("add 3");
+
+        assert_eq!(session.eval("!!"), output("add 3\n8"));
+        assert_eq!(session.eval("!1"), output("add 2\n10"));
+        assert_eq!(
+            session.eval("!9"),
+            Reply::Error("no command 9 in the history".to_owned())
+        );
+        assert_eq!(
+            session.eval("history"),
+            output("   1  add 2\n   2  add 3\n   3  add 3\n   4  add 2\n   5  history")
+        );
+    }
+
+    #[test]
+    fn it_lists_the_commands() {
+        let Reply::Output(help) = Session::<Counter>::new(0).eval("help") else {
+            panic!("no help");
+        };
+
+        assert!(help.starts_with("add [n]  Add n, 1 by default\nhelp     List the commands\n"));
+        assert!(help.contains("\n!<n>     Run command number n of the history again\n"));
+    }
+
+    #[test]
+    fn it_parses_arguments() {
+        assert_eq!(arg::<u32>(&["7"], 0, None, "a number"), Ok(7));
+        assert_eq!(arg(&[], 0, Some(1), "a number"), Ok(1));
+        assert!(arg::<u32>(&[], 0, None, "a number").is_err());
+
+        assert_eq!(point_arg(&["3,4"], 0), Ok(Point::new(3, 4)));
+        assert!(point_arg(&["3;4"], 0).is_err());
+        assert!(point_arg(&[], 0).is_err());
+    }
+
+    #[test]
+    fn it_explores_every_day() {
+        let days = YEARS
+            .iter()
+            .flat_map(|(year, listed)| listed.repls.iter().map(move |repl| (*year, repl)));
+        for (year, &(day, start)) in days {
+            let path = crate::examples::fixtures_dir(year, day).join("example1.txt");
+            let mut session = start(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+            for command in ["help", "show", "reset"] {
+                assert!(
+                    matches!(session.eval(command), Reply::Output(_)),
+                    "day {}: {}",
+                    day,
+                    command
+                );
+            }
+        }
+        assert!(repl(2021, 1).is_none());
+    }
+}
@@ -0,0 +1,503 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::iter;
+use std::path::{Path, PathBuf};
+
+use crate::examples::fixtures_dir;
+use crate::input::input_path;
+use crate::solution::CALENDARS;
+
+/// Source of a new day. `YEAR` is replaced by the year and `DAY` by the zero padded day number.
+const DAY_TEMPLATE: &str = r#"use crate::{Answer, ParseError, Params, Solution};
+
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(str::to_owned).collect())
+}
+
+pub struct DayDAY;
+
+impl Solution for DayDAY {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(_input: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        Err("not solved yet".to_owned())
+    }
+
+    fn part2(_input: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        Err("not solved yet".to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/YEAR/dayDAY/example1.txt");
+
+    #[test]
+    #[ignore = "the example is not there yet"]
+    fn solves_part1_example() {
+        let input = DayDAY::parse(TEST_INPUT).unwrap();
+        assert_eq!(DayDAY::part1(&input, &Params::default()), Ok(Answer::Number(0)));
+    }
+
+    #[test]
+    #[ignore = "the example is not there yet"]
+    fn solves_part2_example() {
+        let input = DayDAY::parse(TEST_INPUT).unwrap();
+        assert_eq!(DayDAY::part2(&input, &Params::default()), Ok(Answer::Number(0)));
+    }
+}
+"#;
+
+/// Binary wrapper of a new day
+const BIN_TEMPLATE: &str = "use std::process::ExitCode;
+
+use aoc::solution::run;
+use aoc::yearYEAR::dayDAY::DayDAY;
+
+fn main() -> ExitCode {
+    run::<DayDAY>()
+}
+";
+
+/// Fuzz targets of a new day, feeding arbitrary input to its parser and to its whole solve path
+const FUZZ_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "parse",
+        "#![no_main]
+
+use aoc::yearYEAR::dayDAY::DayDAY;
+use aoc::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = DayDAY::parse(input);
+});
+",
+    ),
+    (
+        "solve",
+        "#![no_main]
+
+use aoc::options::Options;
+use aoc::solution::solve;
+use aoc::yearYEAR::dayDAY::DayDAY;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<DayDAY>(input, &Options::default());
+});
+",
+    ),
+];
+
+/// Manifest of the fuzz targets, which lists each of them as a binary
+const FUZZ_MANIFEST: &str = "fuzz/Cargo.toml";
+
+/// Source of a new year, with the module of its first day. `DAY` in `dayDAY` is replaced by the
+/// zero padded day number.
+const YEAR_TEMPLATE: &str = "use crate::solution::{read_and_solve, Day, Year};
+use crate::{generate, repl, visual, Solution};
+
+pub mod dayDAY;
+
+/// Everything listed for this year
+pub const YEAR: Year = Year {
+    days: DAYS,
+    generators: GENERATORS,
+    visuals: VISUALS,
+    repls: REPLS,
+};
+
+/// Every day solved this year, in order
+pub const DAYS: &[Day] = &[];
+
+/// Every day with an input generator, in order, with the size of a typical puzzle input
+pub const GENERATORS: &[generate::Entry] = &[];
+
+/// Every day that can be drawn, in order
+pub const VISUALS: &[visual::Entry] = &[];
+
+/// Every day that can be explored, in order
+pub const REPLS: &[repl::Entry] = &[];
+";
+
+/// Start of the list of the days of a year, in its source
+const DAYS_LIST: &str = "pub const DAYS: &[Day] = &[";
+
+/// Start of the list of every year, in `src/solution.rs`
+const YEARS_LIST: &str = "pub const YEARS: &[(u16, Year)] = &[";
+
+/// Formats the line listing a day of a year in a registry
+type Entry = fn(u16, u8) -> String;
+
+/// Files listing every day, and how each of their entries look like. `YEAR` in a file name is
+/// replaced by the year.
+const REGISTRIES: &[(&str, Entry)] = &[
+    ("src/yearYEAR.rs", |_, day| {
+        format!("pub mod day{:02};", day)
+    }),
+    ("benches/days.rs", |year, day| {
+        format!(
+            "    bench_day::<aoc::year{}::day{:02}::Day{:02}>(c, {}, {});",
+            year, day, day, year, day
+        )
+    }),
+];
+
+/// How the library lists the module of a year
+const YEAR_MODULE: Entry = |year, _| format!("pub mod year{};", year);
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day has a source file already
+    Exists(PathBuf),
+    /// A file listing the days doesn't look as expected
+    Registry(PathBuf),
+    Io(io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Registry(path) => {
+                write!(f, "cannot find where to list the day in {}", path.display())
+            }
+            ScaffoldError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(err: io::Error) -> Self {
+        ScaffoldError::Io(err)
+    }
+}
+
+/// Creates the source, binary, fuzz targets, input stub and example fixture of a new day of
+/// `year` inside the crate at `root`, then lists the day everywhere days are listed, starting the
+/// year if it is the first of its days. Returns the files written.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let source = root.join(format!("src/year{}/day{:02}.rs", year, day));
+    if source.exists() {
+        return Err(ScaffoldError::Exists(source));
+    }
+
+    let padded = format!("{:02}", day);
+    let year_source = root.join(format!("src/year{}.rs", year));
+    let registry = |path: &Path| ScaffoldError::Registry(path.to_owned());
+
+    // Work out every change before writing anything, so a failure leaves the tree untouched
+    let mut changes = vec![];
+    if !year_source.exists() {
+        let lib = root.join("src/lib.rs");
+        let contents =
+            add_year_module(&fs::read_to_string(&lib)?, year).ok_or_else(|| registry(&lib))?;
+        changes.push((lib, contents));
+
+        let solution = root.join("src/solution.rs");
+        let contents =
+            add_year(&fs::read_to_string(&solution)?, year).ok_or_else(|| registry(&solution))?;
+        changes.push((solution, contents));
+    }
+
+    for (file, entry) in REGISTRIES {
+        let path = root.join(file.replace("YEAR", &year.to_string()));
+        let contents = if path == year_source && !path.exists() {
+            YEAR_TEMPLATE.replace("dayDAY", &format!("day{}", padded))
+        } else {
+            fs::read_to_string(&path)?
+        };
+
+        let mut contents =
+            add_entry(&contents, *entry, year, day).ok_or_else(|| registry(&path))?;
+        if path == year_source {
+            contents = add_day(&contents, day).ok_or_else(|| registry(&path))?;
+        }
+        changes.push((path, contents));
+    }
+
+    let render = |template: &str| {
+        template
+            .replace("YEAR", &year.to_string())
+            .replace("DAY", &padded)
+    };
+    changes.push((source, render(DAY_TEMPLATE)));
+    changes.push((
+        root.join(format!("src/bin/{}-day{:02}.rs", year, day)),
+        render(BIN_TEMPLATE),
+    ));
+
+    // Fuzz targets, only if the crate is set up for fuzzing
+    let manifest = root.join(FUZZ_MANIFEST);
+    if manifest.exists() {
+        let contents = fs::read_to_string(&manifest)?;
+        changes.push((manifest, add_fuzz_targets(&contents, year, day)));
+
+        for (kind, template) in FUZZ_TEMPLATES {
+            changes.push((
+                root.join(format!(
+                    "fuzz/fuzz_targets/{}.rs",
+                    fuzz_target(kind, year, day)
+                )),
+                render(template),
+            ));
+        }
+    }
+
+    // Inputs and examples are only stubs, never overwrite real ones
+    for stub in [
+        input_path(year, day),
+        fixtures_dir(year, day).join("example1.txt"),
+    ] {
+        let path = root.join(stub);
+        if !path.exists() {
+            changes.push((path, String::new()));
+        }
+    }
+
+    let mut written = vec![];
+    for (path, contents) in changes {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, contents)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// Adds the entry for a day of `year` to a list of entries, one per line, keeping them in order.
+/// Returns `None` if the list isn't found.
+fn add_entry(source: &str, entry: Entry, year: u16, day: u8) -> Option<String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let listed: Vec<(usize, (u16, u8))> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            // Entries that don't name their year, like the modules of its days, are of `year`
+            iter::once(year)
+                .chain(CALENDARS)
+                .flat_map(|y| (1..=25).map(move |d| (y, d)))
+                .find(|&(y, d)| *line == entry(y, d))
+                .map(|key| (i, key))
+        })
+        .collect();
+
+    if listed.iter().any(|(_, key)| *key == (year, day)) {
+        return Some(source.to_owned());
+    }
+
+    let index = match listed.iter().find(|(_, key)| *key > (year, day)) {
+        Some((i, _)) => *i,
+        None => listed.last()?.0 + 1,
+    };
+
+    let new_entry = entry(year, day);
+    lines.insert(index, &new_entry);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Adds the module of `year` to the library. Returns `None` if the modules of the years aren't
+/// found.
+fn add_year_module(source: &str, year: u16) -> Option<String> {
+    // Years are listed as their first day
+    add_entry(source, YEAR_MODULE, year, 1)
+}
+
+/// Adds `day` to the days listed in the source of its year. Returns `None` if the list isn't
+/// found.
+fn add_day(source: &str, day: u8) -> Option<String> {
+    let item = format!(
+        "({}, read_and_solve::<day{:02}::Day{:02}>, day{:02}::Day{:02}::PARAMS)",
+        day, day, day, day, day
+    );
+    add_item(source, DAYS_LIST, item, u16::from(day))
+}
+
+/// Adds `year` to the list of every year. Returns `None` if the list isn't found.
+fn add_year(source: &str, year: u16) -> Option<String> {
+    let item = format!("({}, crate::year{}::YEAR)", year, year);
+    add_item(source, YEARS_LIST, item, year)
+}
+
+/// Adds `item` to the array of tuples starting with `list`, keeping them in order of their first
+/// field, and lays the array out the way rustfmt does. Returns `None` if the array isn't found.
+fn add_item(source: &str, list: &str, item: String, key: u16) -> Option<String> {
+    let start = source.find(list)? + list.len();
+    let end = start + source[start..].find("];")?;
+
+    // The tuples of the array, which end where their outer parenthesis closes
+    let mut items = vec![];
+    let mut depth = 0;
+    let mut open = 0;
+    for (i, c) in source[start..end].char_indices() {
+        match c {
+            '(' => {
+                if depth == 0 {
+                    open = i;
+                }
+                depth += 1;
+            }
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    items.push(source[start..end][open..=i].to_owned());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let keys: Vec<u16> = items
+        .iter()
+        .map(|item| item[1..].split(',').next()?.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    if !keys.contains(&key) {
+        let index = keys.iter().filter(|k| **k < key).count();
+        items.insert(index, item);
+    }
+
+    let single_line = items.join(", ");
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let array = if source[line_start..start].len() + single_line.len() + "];".len() <= 100 {
+        single_line
+    } else {
+        let items: String = items
+            .iter()
+            .map(|item| format!("    {},\n", item))
+            .collect();
+        format!("\n{}", items)
+    };
+
+    Some(format!("{}{}{}", &source[..start], array, &source[end..]))
+}
+
+/// Name of the fuzz target of a kind for a day of `year`
+fn fuzz_target(kind: &str, year: u16, day: u8) -> String {
+    format!("{}_{}_day{:02}", kind, year, day)
+}
+
+/// Appends the `[[bin]]` sections of the fuzz targets of a day of `year` to the fuzz manifest,
+/// unless they are there already
+fn add_fuzz_targets(manifest: &str, year: u16, day: u8) -> String {
+    let mut manifest = manifest.to_owned();
+
+    for (kind, _) in FUZZ_TEMPLATES {
+        let name = fuzz_target(kind, year, day);
+        if manifest.contains(&format!("name = \"{}\"", name)) {
+            continue;
+        }
+
+        if !manifest.ends_with('\n') {
+            manifest.push('\n');
+        }
+        manifest.push_str(&format!(
+            "\n[[bin]]\nname = \"{}\"\npath = \"fuzz_targets/{}.rs\"\ntest = false\ndoc = false\nbench = false\n",
+            name, name
+        ));
+    }
+
+    manifest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_LIB: &str = "pub mod solution;
+
+pub mod year2021;
+
+pub use solution::Solution;
+";
+
+    const TEST_YEAR: &str = "use crate::solution::{read_and_solve, Day};
+
+pub mod day01;
+pub mod day13;
+
+/// Every day solved this year, in order
+pub const DAYS: &[Day] = &[
+    (1, read_and_solve::<day01::Day01>, day01::Day01::PARAMS),
+    (13, read_and_solve::<day13::Day13>, day13::Day13::PARAMS),
+];
+";
+
+    #[test]
+    fn it_lists_days_in_order() {
+        let year = add_entry(TEST_YEAR, REGISTRIES[0].1, 2021, 5).unwrap();
+        assert!(year.contains("pub mod day01;\npub mod day05;\npub mod day13;\n"));
+        assert_eq!(add_entry(&year, REGISTRIES[0].1, 2021, 5).unwrap(), year);
+
+        let benches = "fn days(c: &mut Criterion) {
+    bench_day::<aoc::year2021::day01::Day01>(c, 2021, 1);
+}
+";
+        assert_eq!(
+            add_entry(benches, REGISTRIES[1].1, 2022, 3).unwrap(),
+            "fn days(c: &mut Criterion) {
+    bench_day::<aoc::year2021::day01::Day01>(c, 2021, 1);
+    bench_day::<aoc::year2022::day03::Day03>(c, 2022, 3);
+}
+"
+        );
+
+        assert_eq!(add_entry("fn main() {}", REGISTRIES[1].1, 2021, 14), None);
+    }
+
+    #[test]
+    fn it_lists_days_in_their_year() {
+        let year = add_day(TEST_YEAR, 14).unwrap();
+        assert!(year.contains(
+            "    (13, read_and_solve::<day13::Day13>, day13::Day13::PARAMS),\n    (14, read_and_solve::<day14::Day14>, day14::Day14::PARAMS),\n];"
+        ));
+        assert_eq!(add_day(&year, 14).unwrap(), year);
+
+        // A short list fits on one line, the way rustfmt lays it out
+        let new_year = add_day(&YEAR_TEMPLATE.replace("dayDAY", "day01"), 1).unwrap();
+        assert!(new_year.contains(
+            "pub const DAYS: &[Day] = &[(1, read_and_solve::<day01::Day01>, day01::Day01::PARAMS)];\n"
+        ));
+        assert!(add_day(&new_year, 2).unwrap().contains(
+            "&[\n    (1, read_and_solve::<day01::Day01>, day01::Day01::PARAMS),\n    (2, "
+        ));
+
+        assert_eq!(add_day("fn main() {}", 1), None);
+    }
+
+    #[test]
+    fn it_lists_new_years() {
+        let lib = add_year_module(TEST_LIB, 2022).unwrap();
+        assert!(lib.contains("pub mod year2021;\npub mod year2022;\n"));
+
+        let solution = "pub const YEARS: &[(u16, Year)] = &[(2021, crate::year2021::YEAR)];\n";
+        assert_eq!(
+            add_year(solution, 2020).unwrap(),
+            "pub const YEARS: &[(u16, Year)] = &[(2020, crate::year2020::YEAR), (2021, crate::year2021::YEAR)];\n"
+        );
+        assert_eq!(add_year(solution, 2021).unwrap(), solution);
+    }
+
+    #[test]
+    fn it_adds_fuzz_targets() {
+        let manifest = "[package]\nname = \"aoc-fuzz\"\n";
+        let with_day = add_fuzz_targets(manifest, 2021, 14);
+
+        assert!(with_day.ends_with(
+            "\n[[bin]]\nname = \"solve_2021_day14\"\npath = \"fuzz_targets/solve_2021_day14.rs\"\ntest = false\ndoc = false\nbench = false\n"
+        ));
+        assert_eq!(with_day.matches("[[bin]]").count(), 2);
+        assert_eq!(add_fuzz_targets(&with_day, 2021, 14), with_day);
+    }
+}
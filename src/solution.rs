@@ -0,0 +1,346 @@
+use std::env;
+use std::fmt;
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::input::{ReadError, Source};
+use crate::json;
+use crate::options::{Format, Options, Params};
+use crate::parse::ParseError;
+use crate::{generate, repl, visual};
+
+/// A puzzle solution, split in the stages every day goes through: parse the raw input once, then
+/// solve each part from the parsed input.
+pub trait Solution {
+    /// What the raw input is parsed into
+    type Input;
+
+    /// Names of the parameters the parts take, like `days` for `--days 80`
+    const PARAMS: &'static [&'static str] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Parses the input as it is read from `reader`. By default the input is read whole and given
+    /// to `parse`, but days made of lines or sections parse them as they come instead, so big
+    /// inputs don't have to fit in memory.
+    fn read(reader: &mut dyn BufRead) -> Result<Self::Input, ReadError> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        Ok(Self::parse(&input)?)
+    }
+
+    /// The answer to part 1, or why the input has none, like a number too big for an answer
+    fn part1(input: &Self::Input, params: &Params) -> Result<Answer, String>;
+    fn part2(input: &Self::Input, params: &Params) -> Result<Answer, String>;
+}
+
+/// Answers to the parts of a puzzle, along with how long each stage took
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solved {
+    /// Time taken to read and parse the input, which are done together
+    pub parse_time: Duration,
+    pub parts: Vec<SolvedPart>,
+}
+
+/// The answer to a part, or why the input has none. A part failing leaves the others be.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolvedPart {
+    pub part: u8,
+    pub answer: Result<Answer, String>,
+    pub time: Duration,
+}
+
+impl Solved {
+    /// Whether every part has an answer
+    pub fn is_complete(&self) -> bool {
+        self.parts.iter().all(|part| part.answer.is_ok())
+    }
+
+    /// JSON fields with the parse time and every part's answer and its type, or its error, and
+    /// its time. Times are in nanoseconds.
+    pub fn json_fields(&self) -> Vec<(&'static str, String)> {
+        let parts: Vec<String> = self
+            .parts
+            .iter()
+            .map(|part| {
+                let mut fields = vec![("part", part.part.to_string())];
+                match &part.answer {
+                    Ok(Answer::Number(n)) => {
+                        fields.push(("type", json::quote("number")));
+                        fields.push(("answer", n.to_string()));
+                    }
+                    Ok(Answer::Text(text)) => {
+                        fields.push(("type", json::quote("text")));
+                        fields.push(("answer", json::quote(text)));
+                    }
+                    Err(err) => fields.push(("error", json::quote(err))),
+                }
+                fields.push(("time_ns", part.time.as_nanos().to_string()));
+                json::object(&fields)
+            })
+            .collect();
+
+        vec![
+            ("parse_time_ns", self.parse_time.as_nanos().to_string()),
+            ("parts", json::array(&parts)),
+        ]
+    }
+}
+
+/// Why a day could not be solved. A part with no answer for the input is not one of them, it is
+/// reported with the answers of the other parts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input could not be read
+    Read(String),
+    Parse(ParseError),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Read(err) => write!(f, "cannot read the input: {}", err),
+            SolveError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+    fn from(err: ParseError) -> Self {
+        SolveError::Parse(err)
+    }
+}
+
+impl From<ReadError> for SolveError {
+    fn from(err: ReadError) -> Self {
+        match err {
+            ReadError::Io(err) => SolveError::Read(err.to_string()),
+            ReadError::Parse(err) => SolveError::Parse(err),
+        }
+    }
+}
+
+/// Parses `input` and solves the parts of `S` selected in `options`, in order
+pub fn solve<S: Solution>(input: &str, options: &Options) -> Result<Solved, SolveError> {
+    read_and_solve::<S>(&mut input.as_bytes(), options)
+}
+
+/// Like `solve`, but parses the input while reading it from `reader`
+pub fn read_and_solve<S: Solution>(
+    reader: &mut dyn BufRead,
+    options: &Options,
+) -> Result<Solved, SolveError> {
+    let start = Instant::now();
+    let input = S::read(reader)?;
+    let parse_time = start.elapsed();
+
+    let parts = options
+        .part
+        .numbers()
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1(&input, &options.params),
+                _ => S::part2(&input, &options.params),
+            };
+            SolvedPart {
+                part,
+                answer,
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Solved { parse_time, parts })
+}
+
+/// Reads and parses an input and solves the selected parts, like `read_and_solve` for a given
+/// solution
+pub type Solver = fn(&mut dyn BufRead, &Options) -> Result<Solved, SolveError>;
+
+/// A day of a year, with the names of the parameters it takes
+pub type Day = (u8, Solver, &'static [&'static str]);
+
+/// Years that can have a calendar, from the first Advent of Code on
+pub const CALENDARS: RangeInclusive<u16> = 2015..=2099;
+
+/// What the module of a year lists: the days solved, and those of them that have an input
+/// generator, can be drawn, or can be explored
+#[derive(Clone, Copy)]
+pub struct Year {
+    pub days: &'static [Day],
+    pub generators: &'static [generate::Entry],
+    pub visuals: &'static [visual::Entry],
+    pub repls: &'static [repl::Entry],
+}
+
+/// Every year solved, oldest first
+pub const YEARS: &[(u16, Year)] = &[(2021, crate::year2021::YEAR)];
+
+/// What is listed for `year`, if it has been started
+pub fn find_year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|(n, _)| *n == year).map(|(_, year)| year)
+}
+
+/// The days solved in `year`, if any
+pub fn days(year: u16) -> Option<&'static [Day]> {
+    find_year(year).map(|year| year.days)
+}
+
+/// Year of the days picked without naming one, the latest solved
+pub fn latest_year() -> u16 {
+    YEARS.last().map_or(0, |&(year, _)| year)
+}
+
+/// Entry point for the binary of a single day: reads the inputs named in the arguments (or
+/// stdin), then prints the answer to the selected parts for each of them. Takes the same `--part`
+/// and parameter options as `aoc run`. Errors are reported on stderr.
+pub fn run<S: Solution>() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let (options, paths) = match Options::parse(&args) {
+        Ok((options, rest)) if rest.is_empty() => (options, vec!["-"]),
+        Ok((options, rest)) => (options, rest),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(err) = options.check_params(S::PARAMS) {
+        eprintln!("error: {}", err);
+        return ExitCode::FAILURE;
+    }
+
+    let several = paths.len() > 1;
+    let mut results = vec![];
+    let mut failed = false;
+
+    for path in paths {
+        let result = Source::new(path)
+            .open()
+            .map_err(|err| format!("cannot read {}: {}", path, err))
+            .and_then(|mut reader| {
+                read_and_solve::<S>(&mut reader, &options).map_err(|err| err.to_string())
+            });
+
+        match &result {
+            Ok(solved) => failed |= !solved.is_complete(),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                failed = true;
+            }
+        }
+
+        match (options.format, result) {
+            (Format::Text, Ok(solved)) => {
+                if several {
+                    println!("{}:", path);
+                }
+                for part in solved.parts {
+                    match part.answer {
+                        Ok(answer) => println!("{}", answer),
+                        Err(err) => eprintln!("error: part {}: {}", part.part, err),
+                    }
+                }
+            }
+            (Format::Text, Err(_)) => {}
+            (Format::Json, Ok(solved)) => {
+                let mut fields = solved.json_fields();
+                if several {
+                    fields.insert(0, ("input", json::quote(path)));
+                }
+                results.push(json::object(&fields));
+            }
+            (Format::Json, Err(err)) => {
+                let mut fields = vec![("error", json::quote(&err))];
+                if several {
+                    fields.insert(0, ("input", json::quote(path)));
+                }
+                results.push(json::object(&fields));
+            }
+        }
+    }
+
+    match options.format {
+        Format::Json if several => println!("{}", json::array(&results)),
+        Format::Json => println!("{}", results[0]),
+        Format::Text => {}
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// The answer to one part of a puzzle. Most are numbers, but some puzzles are answered by reading
+/// a text block (like the letters drawn in day 13).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(u64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as u64)
+    }
+}
+
+/// Answers worked out in wider arithmetic, which may not fit
+impl TryFrom<u128> for Answer {
+    type Error = String;
+
+    fn try_from(n: u128) -> Result<Self, String> {
+        (n.try_into())
+            .map(Answer::Number)
+            .map_err(|_| format!("the answer {} does not fit in 64 bits", n))
+    }
+}
+
+impl TryFrom<i128> for Answer {
+    type Error = String;
+
+    fn try_from(n: i128) -> Result<Self, String> {
+        if n < 0 {
+            Err(format!("the answer {} is negative", n))
+        } else {
+            Answer::try_from(n as u128)
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
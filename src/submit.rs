@@ -0,0 +1,623 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::answers::parse_key;
+use crate::client::{Client, ClientError};
+use crate::parse::parse_number;
+use crate::ParseError;
+
+/// Most times an answer is sent while the website keeps asking to wait
+const MAX_TRIES: usize = 3;
+
+/// How the website judged an answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    fn name(self) -> &'static str {
+        match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name().replace('-', " "))
+    }
+}
+
+/// What the website answered to a submission
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Response {
+    /// The answer was judged, and how long to wait before answering again
+    Judged(Verdict, Duration),
+    /// An answer was given too recently, and how long is left to wait
+    TooSoon(Duration),
+    /// The part is solved already, or not unlocked yet
+    WrongLevel,
+}
+
+/// Makes sense of the page the website answers a submission with
+pub fn parse_response(page: &str) -> Option<Response> {
+    let text = article_text(page).to_lowercase();
+    let wait = |start, end| between(&text, start, end).and_then(parse_duration);
+
+    if text.contains("that's the right answer") {
+        Some(Response::Judged(Verdict::Right, Duration::ZERO))
+    } else if text.contains("that's not the right answer") {
+        let verdict = if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        };
+        let wait = wait("please wait ", " before trying again");
+        Some(Response::Judged(
+            verdict,
+            wait.unwrap_or(Duration::from_secs(60)),
+        ))
+    } else if text.contains("you gave an answer too recently") {
+        let wait = wait("you have ", " left to wait");
+        Some(Response::TooSoon(wait.unwrap_or(Duration::from_secs(60))))
+    } else if text.contains("you don't seem to be solving the right level") {
+        Some(Response::WrongLevel)
+    } else {
+        None
+    }
+}
+
+/// The text of the `<article>` of a page, or of the whole page, without tags
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The text right before `end`, from the last `start` before it
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (before, _) = text.split_once(end)?;
+    Some(before.rsplit_once(start)?.1)
+}
+
+/// Parses durations like `4m 28s` or `one minute`
+fn parse_duration(text: &str) -> Option<Duration> {
+    let mut seconds = 0;
+    let mut words = text.split_whitespace();
+
+    while let Some(word) = words.next() {
+        let digits = word
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(word.len());
+        let (count, unit): (u64, &str) = match word.split_at(digits) {
+            ("", "one" | "a" | "an") => (1, words.next()?),
+            ("", _) => return None,
+            (count, "") => (count.parse().ok()?, words.next()?),
+            (count, unit) => (count.parse().ok()?, unit),
+        };
+
+        seconds += count
+            * match unit {
+                "h" | "hour" | "hours" => 3600,
+                "m" | "minute" | "minutes" => 60,
+                "s" | "second" | "seconds" => 1,
+                _ => return None,
+            };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// An answer given to a part of a puzzle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attempt {
+    /// Unix time it was judged at
+    pub time: u64,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub verdict: Verdict,
+    /// Seconds the website asked to wait before answering again
+    pub wait: u64,
+    pub answer: String,
+}
+
+/// Every answer submitted so far, as kept in the `attempts.txt` log.
+///
+/// Every attempt goes in its own `<unix time> <year>/<day>.<part> <verdict> <seconds to wait>
+/// <answer>` line, like `1638680400 2021/05.1 too-high 60 30000`. Verdicts are `right`, `wrong`,
+/// `too-high` or `too-low`. Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default, PartialEq)]
+pub struct Attempts {
+    attempts: Vec<Attempt>,
+}
+
+/// Why an answer isn't worth submitting, going by the previous attempts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Known {
+    /// The part was solved with this answer
+    Solved(String),
+    /// This very answer was tried already
+    Tried(Verdict),
+    /// An answer this low or lower was too high already
+    TooHigh(u64),
+    /// An answer this high or higher was too low already
+    TooLow(u64),
+}
+
+impl fmt::Display for Known {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Known::Solved(answer) => write!(f, "the part is solved already, with `{}`", answer),
+            Known::Tried(verdict) => {
+                write!(f, "this answer was tried already, and was {}", verdict)
+            }
+            Known::TooHigh(answer) => write!(f, "`{}` was too high already", answer),
+            Known::TooLow(answer) => write!(f, "`{}` was too low already", answer),
+        }
+    }
+}
+
+impl Attempts {
+    pub fn push(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt)
+    }
+
+    fn of_part(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        (self.attempts.iter())
+            .filter(move |attempt| (attempt.year, attempt.day, attempt.part) == (year, day, part))
+    }
+
+    /// Whether the previous attempts at a part already tell how `answer` would be judged
+    pub fn check(&self, year: u16, day: u8, part: u8, answer: &str) -> Option<Known> {
+        let number = answer.parse::<u64>().ok();
+
+        if let Some(right) = self
+            .of_part(year, day, part)
+            .find(|a| a.verdict == Verdict::Right)
+        {
+            return Some(Known::Solved(right.answer.clone()));
+        }
+        if let Some(tried) = self.of_part(year, day, part).find(|a| a.answer == answer) {
+            return Some(Known::Tried(tried.verdict));
+        }
+
+        self.of_part(year, day, part).find_map(|attempt| {
+            match (attempt.verdict, attempt.answer.parse::<u64>().ok(), number) {
+                (Verdict::TooHigh, Some(high), Some(n)) if n >= high => Some(Known::TooHigh(high)),
+                (Verdict::TooLow, Some(low), Some(n)) if n <= low => Some(Known::TooLow(low)),
+                _ => None,
+            }
+        })
+    }
+
+    /// Unix time from which the website takes answers to a part again
+    pub fn ready_at(&self, year: u16, day: u8, part: u8) -> u64 {
+        self.of_part(year, day, part)
+            .map(|attempt| attempt.time + attempt.wait)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl FromStr for Attempts {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let mut attempts = vec![];
+
+        for (n, line) in input.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            attempts.push(parse_attempt(line).map_err(|err| err.offset_lines(n))?);
+        }
+
+        Ok(Attempts { attempts })
+    }
+}
+
+fn parse_attempt(line: &str) -> Result<Attempt, ParseError> {
+    let fields: Vec<&str> = line.splitn(5, ' ').collect();
+    let [time, key, verdict, wait, answer] = fields[..] else {
+        return Err(ParseError::new(
+            1,
+            "an attempt like `1638680400 2021/05.1 too-high 60 30000`",
+        ));
+    };
+
+    let (year, day, part) = parse_key(line, key)?;
+
+    let verdict = [
+        Verdict::Right,
+        Verdict::Wrong,
+        Verdict::TooHigh,
+        Verdict::TooLow,
+    ]
+    .into_iter()
+    .find(|known| known.name() == verdict)
+    .ok_or_else(|| ParseError::at(line, verdict, "`right`, `wrong`, `too-high` or `too-low`"))?;
+
+    Ok(Attempt {
+        time: parse_number(line, time)?,
+        year,
+        day,
+        part,
+        verdict,
+        wait: parse_number(line, wait)?,
+        answer: answer.to_owned(),
+    })
+}
+
+impl fmt::Display for Attempts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# Answers given with `aoc submit`, as `<unix time> <year>/<day>.<part> <verdict> \
+             <seconds to wait> <answer>`"
+        )?;
+        for attempt in &self.attempts {
+            writeln!(
+                f,
+                "{} {}/{:02}.{} {} {} {}",
+                attempt.time,
+                attempt.year,
+                attempt.day,
+                attempt.part,
+                attempt.verdict.name(),
+                attempt.wait,
+                attempt.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Tells the time and waits, so submitting can be tested without waiting
+pub trait Clock {
+    /// Unix time, in seconds
+    fn now(&self) -> u64;
+    fn sleep(&mut self, duration: Duration);
+}
+
+/// What came of submitting an answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Not submitted, as the previous attempts tell how it would go
+    Known(Known),
+    Judged(Verdict),
+    /// The website doesn't take answers to the part
+    WrongLevel,
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Client(ClientError),
+    /// The website answered with a page that doesn't say how the answer went
+    Unexpected(String),
+    /// The website kept asking to wait
+    TooSoon,
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Client(err) => write!(f, "{}", err),
+            SubmitError::Unexpected(text) => write!(f, "unexpected answer: {}", text),
+            SubmitError::TooSoon => write!(
+                f,
+                "the website still asks to wait after {} tries",
+                MAX_TRIES
+            ),
+        }
+    }
+}
+
+impl Error for SubmitError {}
+
+impl From<ClientError> for SubmitError {
+    fn from(err: ClientError) -> Self {
+        SubmitError::Client(err)
+    }
+}
+
+/// Submits `answer` to a part unless the previous attempts tell how it would go, waiting first
+/// as long as the website asked to, and records how it was judged
+pub fn submit(
+    client: &Client,
+    attempts: &mut Attempts,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+    clock: &mut dyn Clock,
+) -> Result<Outcome, SubmitError> {
+    if let Some(known) = attempts.check(year, day, part, answer) {
+        return Ok(Outcome::Known(known));
+    }
+
+    let (ready_at, now) = (attempts.ready_at(year, day, part), clock.now());
+    if ready_at > now {
+        clock.sleep(Duration::from_secs(ready_at - now));
+    }
+
+    for _ in 0..MAX_TRIES {
+        let page = client.answer(year, day, part, answer)?;
+
+        match parse_response(&page) {
+            Some(Response::Judged(verdict, wait)) => {
+                attempts.push(Attempt {
+                    time: clock.now(),
+                    year,
+                    day,
+                    part,
+                    verdict,
+                    wait: wait.as_secs(),
+                    answer: answer.to_owned(),
+                });
+                return Ok(Outcome::Judged(verdict));
+            }
+            // The time left is rounded down, so wait a second longer
+            Some(Response::TooSoon(wait)) => clock.sleep(wait + Duration::from_secs(1)),
+            Some(Response::WrongLevel) => return Ok(Outcome::WrongLevel),
+            None => return Err(SubmitError::Unexpected(article_text(&page))),
+        }
+    }
+
+    Err(SubmitError::TooSoon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::{serve, serve_once, test_config};
+    use std::cell::Cell;
+
+    const RIGHT: &str = "<main><article><p>That's the right answer! You are one gold star \
+                         closer to saving your vacation.</p></article></main>";
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too \
+                            high.  If you're stuck, make sure you're using the full input data. \
+                            Please wait one minute before trying again.</p></article></main>";
+    const TOO_SOON: &str = "<main><article><p>You gave an answer too recently; you have to wait \
+                            after submitting an answer before trying again.  You have 4m 28s left \
+                            to wait. <a href=\"/2021/day/5\">[Return to Day 5]</a></p></article>\
+                            </main>";
+    const WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did \
+                               you already complete it?</p></article>";
+
+    /// A clock that only moves when slept on
+    struct FakeClock {
+        now: u64,
+        slept: Vec<u64>,
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> u64 {
+            self.now
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now += duration.as_secs();
+            self.slept.push(duration.as_secs());
+        }
+    }
+
+    fn clock() -> FakeClock {
+        FakeClock {
+            now: 1_000_000,
+            slept: vec![],
+        }
+    }
+
+    fn attempt(verdict: Verdict, answer: &str) -> Attempt {
+        Attempt {
+            time: 1_000_000,
+            year: 2021,
+            day: 5,
+            part: 1,
+            verdict,
+            wait: 60,
+            answer: answer.to_owned(),
+        }
+    }
+
+    #[test]
+    fn it_parses_responses() {
+        assert_eq!(
+            parse_response(RIGHT),
+            Some(Response::Judged(Verdict::Right, Duration::ZERO))
+        );
+        assert_eq!(
+            parse_response(TOO_HIGH),
+            Some(Response::Judged(Verdict::TooHigh, Duration::from_secs(60)))
+        );
+        assert_eq!(
+            parse_response(TOO_SOON),
+            Some(Response::TooSoon(Duration::from_secs(268)))
+        );
+        assert_eq!(parse_response(WRONG_LEVEL), Some(Response::WrongLevel));
+        assert_eq!(parse_response("<article>Huh?</article>"), None);
+    }
+
+    #[test]
+    fn it_parses_durations() {
+        assert_eq!(parse_duration("5 minutes"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h 2m 3s"), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_duration("a while"), None);
+    }
+
+    #[test]
+    fn it_parses_and_prints_the_log() {
+        let log =
+            "# Answers\n1000000 2021/05.1 too-high 60 30000\n\n1000100 2021/13.2 right 0 ABC DEF\n";
+        let attempts: Attempts = log.parse().unwrap();
+
+        assert_eq!(attempts.attempts[0], attempt(Verdict::TooHigh, "30000"));
+        assert_eq!(attempts.attempts[1].answer, "ABC DEF");
+        assert_eq!(attempts.to_string().parse(), Ok(attempts));
+
+        assert_eq!(
+            "1000000 2021/05.1 too-big 60 30000".parse::<Attempts>(),
+            Err(ParseError::new(
+                19,
+                "`right`, `wrong`, `too-high` or `too-low`"
+            ))
+        );
+        assert_eq!(
+            "\n1000000 2021/05.3 wrong 60 1".parse::<Attempts>(),
+            Err(ParseError::new(17, "part 1 or 2").offset_lines(1))
+        );
+    }
+
+    #[test]
+    fn it_knows_answers_not_worth_submitting() {
+        let mut attempts = Attempts::default();
+        attempts.push(attempt(Verdict::TooHigh, "30000"));
+        attempts.push(attempt(Verdict::TooLow, "100"));
+        attempts.push(attempt(Verdict::Wrong, "5000"));
+
+        assert_eq!(
+            attempts.check(2021, 5, 1, "30001"),
+            Some(Known::TooHigh(30000))
+        );
+        assert_eq!(attempts.check(2021, 5, 1, "99"), Some(Known::TooLow(100)));
+        assert_eq!(
+            attempts.check(2021, 5, 1, "5000"),
+            Some(Known::Tried(Verdict::Wrong))
+        );
+        assert_eq!(attempts.check(2021, 5, 1, "5001"), None);
+        assert_eq!(attempts.check(2021, 5, 2, "30001"), None);
+        assert_eq!(attempts.ready_at(2021, 5, 1), 1_000_060);
+
+        attempts.push(attempt(Verdict::Right, "5084"));
+        assert_eq!(
+            attempts.check(2021, 5, 1, "5001"),
+            Some(Known::Solved("5084".to_owned()))
+        );
+    }
+
+    #[test]
+    fn it_submits_and_records_the_verdict() {
+        let (base_url, server) = serve_once(200, TOO_HIGH);
+        let client = Client::new(&test_config(&base_url)).unwrap();
+        let mut attempts = Attempts::default();
+        let mut clock = clock();
+
+        assert_eq!(
+            submit(&client, &mut attempts, 2021, 5, 1, "30000", &mut clock).unwrap(),
+            Outcome::Judged(Verdict::TooHigh)
+        );
+        assert!(server.join().unwrap().ends_with("level=1&answer=30000"));
+        assert_eq!(attempts.attempts, [attempt(Verdict::TooHigh, "30000")]);
+
+        // Known already, so the server isn't asked again
+        assert_eq!(
+            submit(&client, &mut attempts, 2021, 5, 1, "40000", &mut clock).unwrap(),
+            Outcome::Known(Known::TooHigh(30000))
+        );
+    }
+
+    #[test]
+    fn it_waits_as_long_as_the_website_asks() {
+        let (base_url, server) = serve(&[(200, TOO_SOON), (200, RIGHT)]);
+        let client = Client::new(&test_config(&base_url)).unwrap();
+        let mut attempts = Attempts::default();
+        attempts.push(attempt(Verdict::TooLow, "100"));
+        let mut clock = clock();
+        clock.now += 20;
+
+        assert_eq!(
+            submit(&client, &mut attempts, 2021, 5, 1, "5084", &mut clock).unwrap(),
+            Outcome::Judged(Verdict::Right)
+        );
+        assert_eq!(server.join().unwrap().len(), 2);
+
+        // The 40 seconds left from the last attempt, then what the website asked for
+        assert_eq!(clock.slept, [40, 269]);
+        assert_eq!(attempts.attempts[1].time, 1_000_329);
+    }
+
+    /// A clock that moves on by `tick` seconds every time it's read
+    struct TickingClock {
+        now: Cell<u64>,
+        tick: u64,
+        slept: Vec<u64>,
+    }
+
+    impl Clock for TickingClock {
+        fn now(&self) -> u64 {
+            self.now.set(self.now.get() + self.tick);
+            self.now.get()
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now.set(self.now.get() + duration.as_secs());
+            self.slept.push(duration.as_secs());
+        }
+    }
+
+    #[test]
+    fn it_waits_on_a_clock_that_moves_on_its_own() {
+        let (base_url, server) = serve_once(200, RIGHT);
+        let client = Client::new(&test_config(&base_url)).unwrap();
+        let mut attempts = Attempts::default();
+        attempts.push(attempt(Verdict::TooLow, "100"));
+        let mut clock = TickingClock {
+            now: Cell::new(1_000_054),
+            tick: 5,
+            slept: vec![],
+        };
+
+        // Reading the clock a second time would be past the time to wait until
+        assert_eq!(
+            submit(&client, &mut attempts, 2021, 5, 1, "5084", &mut clock).unwrap(),
+            Outcome::Judged(Verdict::Right)
+        );
+        server.join().unwrap();
+        assert_eq!(clock.slept, [1]);
+    }
+
+    #[test]
+    fn it_reports_unexpected_pages() {
+        let (base_url, server) = serve_once(200, "<article><p>Huh?</p></article>");
+        let client = Client::new(&test_config(&base_url)).unwrap();
+
+        let result = submit(
+            &client,
+            &mut Attempts::default(),
+            2021,
+            5,
+            1,
+            "1",
+            &mut clock(),
+        );
+        assert_eq!(result.unwrap_err().to_string(), "unexpected answer: Huh?");
+        server.join().unwrap();
+    }
+}
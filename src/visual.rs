@@ -0,0 +1,516 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::grid::Grid;
+use crate::solution::find_year;
+use crate::{Params, ParseError, Solution};
+
+/// An RGB colour
+pub type Colour = [u8; 3];
+
+/// A picture of a puzzle while it runs. Every cell holds a level, drawn with the colour at that
+/// index of the palette.
+pub type Frame = Grid<u8>;
+
+/// Days that can be drawn while they run
+pub trait Visualize: Solution {
+    /// Colour of every level a cell can be in, from the lowest one
+    const COLOURS: &'static [Colour];
+
+    /// Runs the puzzle on `input`, recording a frame at every step worth seeing. Fails if the
+    /// frames would be too big to draw.
+    fn record(input: &Self::Input, params: &Params, recorder: &mut Recorder) -> Result<(), String>;
+}
+
+/// Most cells a frame can have, so an input spread over a huge area can't take all the memory
+pub const MAX_FRAME_CELLS: usize = 1 << 22;
+
+/// Checks that a picture of `width` × `height` cells is small enough to draw
+pub fn check_size(width: usize, height: usize) -> Result<(), String> {
+    match width.checked_mul(height) {
+        Some(cells) if cells <= MAX_FRAME_CELLS => Ok(()),
+        _ => Err(format!(
+            "{}×{} cells are too many to draw, the most is {}",
+            width, height, MAX_FRAME_CELLS
+        )),
+    }
+}
+
+/// Most pixels a drawn frame can have, so a big `scale` can't take all the memory either
+pub const MAX_FRAME_PIXELS: usize = 1 << 26;
+
+/// Width and height in pixels of a picture of `width` × `height` cells, each `scale` pixels on a
+/// side, if it has at most `MAX_FRAME_PIXELS`
+fn scaled(width: usize, height: usize, scale: usize) -> Option<(usize, usize)> {
+    let (pixel_width, pixel_height) = (width.checked_mul(scale)?, height.checked_mul(scale)?);
+    match pixel_width.checked_mul(pixel_height) {
+        Some(pixels) if pixels <= MAX_FRAME_PIXELS => Some((pixel_width, pixel_height)),
+        _ => None,
+    }
+}
+
+/// Checks that a picture of `width` × `height` cells is small enough to draw with `scale` pixels
+/// per cell on each side
+pub fn check_scale(width: usize, height: usize, scale: usize) -> Result<(), String> {
+    match scaled(width, height, scale) {
+        Some(_) => Ok(()),
+        None => Err(format!(
+            "{}×{} cells at a scale of {} are too many pixels to draw, the most is {}",
+            width, height, scale, MAX_FRAME_PIXELS
+        )),
+    }
+}
+
+/// Why a day could not be drawn
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DrawError {
+    Parse(ParseError),
+    /// The frames of the run are too big to draw
+    Record(String),
+}
+
+impl fmt::Display for DrawError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DrawError::Parse(err) => write!(f, "{}", err),
+            DrawError::Record(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for DrawError {}
+
+/// Most cells all the frames of a run can have together, so a run with many steps can't take all
+/// the memory
+pub const MAX_RECORDED_CELLS: usize = 1 << 27;
+
+/// Collects the frames of a run
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    frames: Vec<Frame>,
+    cells: usize,
+    max_cells: usize,
+}
+
+impl Default for Recorder {
+    fn default() -> Recorder {
+        Recorder::with_limit(MAX_RECORDED_CELLS)
+    }
+}
+
+impl Recorder {
+    pub fn new() -> Recorder {
+        Recorder::default()
+    }
+
+    /// A recorder whose frames can have at most `max_cells` cells together
+    pub fn with_limit(max_cells: usize) -> Recorder {
+        Recorder {
+            frames: vec![],
+            cells: 0,
+            max_cells,
+        }
+    }
+
+    /// Adds `frame` to the run. Fails, leaving it out, if the frames would have too many cells.
+    pub fn record(&mut self, frame: Frame) -> Result<(), String> {
+        match self.cells.checked_add(frame.len()) {
+            Some(cells) if cells <= self.max_cells => {
+                self.cells = cells;
+                self.frames.push(frame);
+                Ok(())
+            }
+            _ => Err(format!(
+                "the run takes more than {} frames, too many cells to record: the most is {} \
+                 in all",
+                self.frames.len(),
+                self.max_cells
+            )),
+        }
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn into_frames(self) -> Vec<Frame> {
+        self.frames
+    }
+}
+
+/// Parses `input` and records the frames of a run of the day on it
+pub fn record<S: Visualize>(input: &str, params: &Params) -> Result<Vec<Frame>, DrawError> {
+    let input = S::parse(input).map_err(DrawError::Parse)?;
+    let mut recorder = Recorder::new();
+    S::record(&input, params, &mut recorder).map_err(DrawError::Record)?;
+    Ok(recorder.into_frames())
+}
+
+/// Records the frames of a day from its raw input
+pub type Recording = fn(&str, &Params) -> Result<Vec<Frame>, DrawError>;
+
+/// A day that can be drawn, with the parameters it takes and its default colours
+pub type Entry = (u8, Recording, &'static [&'static str], &'static [Colour]);
+
+/// How a day drawn by `S` is listed in its year
+pub const fn entry<S: Visualize>(day: u8) -> Entry {
+    (day, record::<S>, S::PARAMS, S::COLOURS)
+}
+
+/// The recording of a day of `year`, the parameters it takes, and its default palette
+pub fn visual(year: u16, day: u8) -> Option<(Recording, &'static [&'static str], Palette)> {
+    find_year(year)?
+        .visuals
+        .iter()
+        .find(|(n, _, _, _)| *n == day)
+        .map(|&(_, record, params, colours)| (record, params, Palette::new(colours.to_vec())))
+}
+
+/// The colours of the levels of a frame, from the lowest one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colours: Vec<Colour>,
+}
+
+impl Palette {
+    pub fn new(colours: Vec<Colour>) -> Palette {
+        assert!(
+            (1..=256).contains(&colours.len()),
+            "a palette needs from 1 to 256 colours"
+        );
+        Palette { colours }
+    }
+
+    /// `levels` colours blending evenly from each of `stops` into the next
+    pub fn gradient(stops: &[Colour], levels: usize) -> Palette {
+        let colours = (0..levels)
+            .map(|level| {
+                if levels == 1 || stops.len() == 1 {
+                    return stops[0];
+                }
+
+                // Where the level falls between the stops, in steps of 1/(levels - 1)
+                let position = level * (stops.len() - 1);
+                let (stop, offset) = (position / (levels - 1), position % (levels - 1));
+                if offset == 0 {
+                    return stops[stop];
+                }
+
+                let (from, to) = (stops[stop], stops[stop + 1]);
+                let mut colour = [0; 3];
+                for channel in 0..3 {
+                    let (from, to) = (from[channel] as usize, to[channel] as usize);
+                    colour[channel] =
+                        ((from * (levels - 1 - offset) + to * offset) / (levels - 1)) as u8;
+                }
+                colour
+            })
+            .collect();
+
+        Palette::new(colours)
+    }
+
+    pub fn colours(&self) -> &[Colour] {
+        &self.colours
+    }
+
+    /// The colours one channel after another, like `r, g, b, r, g, b, ...`
+    fn channels(&self) -> Vec<u8> {
+        self.colours.iter().flatten().copied().collect()
+    }
+}
+
+/// Parses colours like `1a1c2c,#ffcd75`, separated by commas
+impl FromStr for Palette {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let colours = input
+            .split(',')
+            .map(|colour| parse_colour(input, colour.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if colours.len() > 256 {
+            return Err(ParseError::new(input.len() + 1, "at most 256 colours"));
+        }
+
+        Ok(Palette::new(colours))
+    }
+}
+
+/// Parses `part` (a slice of `line`) as a hex colour like `ff8800` or `#ff8800`
+fn parse_colour(line: &str, part: &str) -> Result<Colour, ParseError> {
+    let hex = part.strip_prefix('#').unwrap_or(part);
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+    };
+
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok([r, g, b]),
+        _ => Err(ParseError::at(line, part, "a colour like `ff8800`")),
+    }
+}
+
+/// Palette indices of the pixels of `frame` on a canvas of `width` by `height` cells, each cell
+/// blown up to a `scale` by `scale` square. Whatever the frame doesn't cover is left at level 0,
+/// and levels past the end of the palette get its last colour. Fails if the canvas has too many
+/// pixels.
+fn pixels(
+    frame: &Frame,
+    palette: &Palette,
+    scale: usize,
+    width: usize,
+    height: usize,
+) -> io::Result<Vec<u8>> {
+    let (pixel_width, pixel_height) = pixel_size(width, height, scale)?;
+    let last = (palette.colours.len() - 1) as u8;
+    let mut pixels = Vec::with_capacity(pixel_width * pixel_height);
+
+    for y in 0..height {
+        let mut row = Vec::with_capacity(pixel_width);
+        for x in 0..width {
+            let level = if y < frame.height() && x < frame.width() {
+                frame.row(y)[x].min(last)
+            } else {
+                0
+            };
+            row.extend(std::iter::repeat_n(level, scale));
+        }
+
+        for _ in 0..scale {
+            pixels.extend_from_slice(&row);
+        }
+    }
+
+    Ok(pixels)
+}
+
+/// Like `scaled`, failing with why the picture can't be drawn
+fn pixel_size(width: usize, height: usize, scale: usize) -> io::Result<(usize, usize)> {
+    scaled(width, height, scale).ok_or_else(|| {
+        let err = check_scale(width, height, scale).unwrap_err();
+        io::Error::new(io::ErrorKind::InvalidInput, err)
+    })
+}
+
+/// Writes `frame` as a PNG image, with `scale` by `scale` pixels per cell
+pub fn write_png<W: Write>(
+    out: W,
+    frame: &Frame,
+    palette: &Palette,
+    scale: usize,
+) -> io::Result<()> {
+    let (width, height) = pixel_size(frame.width(), frame.height(), scale)?;
+    let size = |side: usize| u32::try_from(side).map_err(|_| too_big("a PNG"));
+
+    let mut encoder = png::Encoder::new(out, size(width)?, size(height)?);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette.channels());
+
+    let pixels = pixels(frame, palette, scale, frame.width(), frame.height())?;
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&pixels).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// Writes every frame in `frames` as an animated GIF that loops forever, showing each frame for
+/// `delay`. Frames smaller than the biggest one are drawn on its top left corner.
+pub fn write_gif<W: Write>(
+    out: W,
+    frames: &[Frame],
+    palette: &Palette,
+    scale: usize,
+    delay: Duration,
+) -> io::Result<()> {
+    let width = frames.iter().map(Grid::width).max().unwrap_or(0);
+    let height = frames.iter().map(Grid::height).max().unwrap_or(0);
+    let (pixel_width, pixel_height) = pixel_size(width, height, scale)?;
+    let size = |side: usize| u16::try_from(side).map_err(|_| too_big("a GIF"));
+    let (gif_width, gif_height) = (size(pixel_width)?, size(pixel_height)?);
+
+    let mut encoder = gif::Encoder::new(out, gif_width, gif_height, &palette.channels())
+        .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    // GIF delays are counted in hundredths of a second
+    let delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+
+    for frame in frames {
+        let gif_frame = gif::Frame {
+            width: gif_width,
+            height: gif_height,
+            delay,
+            buffer: Cow::Owned(pixels(frame, palette, scale, width, height)?),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+/// Writes every frame in `frames` as `frame0000.png`, `frame0001.png`... inside `dir`, creating
+/// it if needed. Returns the files written.
+pub fn write_pngs(
+    dir: &Path,
+    frames: &[Frame],
+    palette: &Palette,
+    scale: usize,
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+
+    let mut written = vec![];
+    for (i, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("frame{:04}.png", i));
+        let mut out = BufWriter::new(File::create(&path)?);
+        write_png(&mut out, frame, palette, scale)?;
+        out.flush()?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+fn too_big(format: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("the frames are too big for {}", format),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::YEARS;
+
+    const BLACK: Colour = [0, 0, 0];
+    const WHITE: Colour = [255, 255, 255];
+
+    #[test]
+    fn it_blends_gradients() {
+        assert_eq!(
+            Palette::gradient(&[BLACK, WHITE], 3).colours(),
+            [BLACK, [127, 127, 127], WHITE]
+        );
+        assert_eq!(
+            Palette::gradient(&[BLACK, [0, 0, 200], WHITE], 5).colours(),
+            [BLACK, [0, 0, 100], [0, 0, 200], [127, 127, 227], WHITE]
+        );
+        assert_eq!(Palette::gradient(&[WHITE], 2).colours(), [WHITE, WHITE]);
+    }
+
+    #[test]
+    fn it_parses_palettes() {
+        assert_eq!(
+            "000000, #ffcc00".parse(),
+            Ok(Palette::new(vec![BLACK, [255, 204, 0]]))
+        );
+        assert_eq!(
+            "000000,ffcc0".parse::<Palette>(),
+            Err(ParseError::new(8, "a colour like `ff8800`"))
+        );
+        assert_eq!(
+            "".parse::<Palette>(),
+            Err(ParseError::new(1, "a colour like `ff8800`"))
+        );
+    }
+
+    fn checkers() -> Frame {
+        Grid::from_rows(vec![vec![0, 1, 0], vec![1, 0, 1]]).unwrap()
+    }
+
+    #[test]
+    fn it_writes_scaled_pngs() {
+        let palette = Palette::new(vec![BLACK, WHITE]);
+        let mut png = vec![];
+        write_png(&mut png, &checkers(), &palette, 2).unwrap();
+
+        let decoder = png::Decoder::new(&png[..]);
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((info.width, info.height), (6, 4));
+        assert_eq!(info.color_type, png::ColorType::Indexed);
+        assert_eq!(&pixels[..6], [0, 0, 1, 1, 0, 0]);
+        assert_eq!(&pixels[6..12], [0, 0, 1, 1, 0, 0]);
+        assert_eq!(&pixels[12..18], [1, 1, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn it_writes_animated_gifs() {
+        let palette = Palette::new(vec![BLACK, WHITE, [255, 0, 0]]);
+        let small = Grid::from_rows(vec![vec![7]]).unwrap();
+        let mut gif = vec![];
+        write_gif(
+            &mut gif,
+            &[checkers(), small],
+            &palette,
+            1,
+            Duration::from_millis(50),
+        )
+        .unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(&gif[..]).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (3, 2));
+
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(first.delay, 5);
+        assert_eq!(&first.buffer[..], [0, 1, 0, 1, 0, 1]);
+
+        // Smaller frames are padded, and levels past the palette get its last colour
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(&second.buffer[..], [2, 0, 0, 0, 0, 0]);
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn it_refuses_scales_with_too_many_pixels() {
+        let palette = Palette::new(vec![BLACK, WHITE]);
+        assert!(check_scale(3, 2, 1000).is_ok());
+        assert!(check_scale(3, 2, 10_000).is_err());
+        assert!(check_scale(3, 2, usize::MAX).is_err());
+
+        // Refused before multiplying, instead of overflowing or running out of memory
+        let err = write_png(vec![], &checkers(), &palette, usize::MAX).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let err = write_gif(vec![], &[checkers()], &palette, 1 << 40, Duration::ZERO).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn it_records_every_visual_day() {
+        let days = YEARS
+            .iter()
+            .flat_map(|(year, listed)| listed.visuals.iter().map(move |visual| (*year, visual)));
+        for (year, &(day, record, _, colours)) in days {
+            let path = crate::examples::fixtures_dir(year, day).join("example1.txt");
+            let input = fs::read_to_string(&path).unwrap();
+            let frames = record(&input, &Params::default()).unwrap();
+
+            assert!(
+                frames.len() > 1,
+                "day {} recorded {} frames",
+                day,
+                frames.len()
+            );
+            for frame in &frames {
+                assert!(frame
+                    .cells()
+                    .all(|(_, &level)| (level as usize) < colours.len()));
+            }
+        }
+        assert!(visual(2021, 1).is_none());
+    }
+}
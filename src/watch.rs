@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Notices changes to a set of files by polling their modification times
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+
+        Watcher { files }
+    }
+
+    /// Files modified, created or removed since the last call (or since the watcher was made)
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = vec![];
+
+        for (path, last_modified) in self.files.iter_mut() {
+            let modified = modified(path);
+            if modified != *last_modified {
+                *last_modified = modified;
+                changed.push(path.clone());
+            }
+        }
+
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// What a `cargo test` run amounts to
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: usize,
+    pub failed: usize,
+    /// Names of the failing tests
+    pub failures: Vec<String>,
+}
+
+impl TestSummary {
+    /// Adds up every `test result:` line in the output of `cargo test`, and collects the names
+    /// listed under its last `failures:` header. Returns `None` if no tests ran, which means they
+    /// didn't build.
+    pub fn from_output(output: &str) -> Option<TestSummary> {
+        let mut summary = TestSummary::default();
+        let mut ran = false;
+
+        for line in output.lines() {
+            if let Some(result) = line.strip_prefix("test result: ") {
+                ran = true;
+                for count in result.split(['.', ';']) {
+                    if let Some(n) = count.trim().strip_suffix(" passed") {
+                        summary.passed += n.parse::<usize>().unwrap_or(0);
+                    }
+                    if let Some(n) = count.trim().strip_suffix(" failed") {
+                        summary.failed += n.parse::<usize>().unwrap_or(0);
+                    }
+                }
+            }
+        }
+
+        if let Some(start) = output.rfind("\nfailures:\n") {
+            summary.failures = output[start + "\nfailures:\n".len()..]
+                .lines()
+                .take_while(|line| line.starts_with("    "))
+                .map(|line| line.trim().to_owned())
+                .collect();
+        }
+
+        ran.then_some(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_OUTPUT: &str = "
+running 4 tests
+..F.
+failures:
+
+---- day08::tests::it_decodes stdout ----
+thread 'day08::tests::it_decodes' panicked at src/day08.rs:10:5
+
+failures:
+    day08::tests::it_decodes
+
+test result: FAILED. 3 passed; 1 failed; 0 ignored; 0 measured; 70 filtered out; finished in 0.01s
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+    #[test]
+    fn it_summarizes_test_runs() {
+        assert_eq!(
+            TestSummary::from_output(TEST_OUTPUT),
+            Some(TestSummary {
+                passed: 3,
+                failed: 1,
+                failures: vec!["day08::tests::it_decodes".to_owned()],
+            })
+        );
+        assert_eq!(
+            TestSummary::from_output("error[E0308]: mismatched types"),
+            None
+        );
+    }
+
+    #[test]
+    fn it_notices_changes() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&path, "3,4,3,1,2").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), vec![path]);
+    }
+}
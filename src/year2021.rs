@@ -0,0 +1,76 @@
+use crate::repl::{self, session};
+use crate::solution::{read_and_solve, Day, Year};
+use crate::visual;
+use crate::Solution;
+
+pub mod generate;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day13;
+
+/// Everything listed for this year
+pub const YEAR: Year = Year {
+    days: DAYS,
+    generators: GENERATORS,
+    visuals: VISUALS,
+    repls: REPLS,
+};
+
+/// Every day solved this year, in order
+pub const DAYS: &[Day] = &[
+    (1, read_and_solve::<day01::Day01>, day01::Day01::PARAMS),
+    (2, read_and_solve::<day02::Day02>, day02::Day02::PARAMS),
+    (3, read_and_solve::<day03::Day03>, day03::Day03::PARAMS),
+    (4, read_and_solve::<day04::Day04>, day04::Day04::PARAMS),
+    (5, read_and_solve::<day05::Day05>, day05::Day05::PARAMS),
+    (6, read_and_solve::<day06::Day06>, day06::Day06::PARAMS),
+    (7, read_and_solve::<day07::Day07>, day07::Day07::PARAMS),
+    (8, read_and_solve::<day08::Day08>, day08::Day08::PARAMS),
+    (9, read_and_solve::<day09::Day09>, day09::Day09::PARAMS),
+    (10, read_and_solve::<day10::Day10>, day10::Day10::PARAMS),
+    (11, read_and_solve::<day11::Day11>, day11::Day11::PARAMS),
+    (13, read_and_solve::<day13::Day13>, day13::Day13::PARAMS),
+];
+
+/// Every day with an input generator, in order, with the size of a typical puzzle input
+pub const GENERATORS: &[crate::generate::Entry] = &[
+    (1, generate::day01, 2000),
+    (2, generate::day02, 1000),
+    (3, generate::day03, 1000),
+    (4, generate::day04, 100),
+    (5, generate::day05, 500),
+    (6, generate::day06, 300),
+    (7, generate::day07, 1000),
+    (8, generate::day08, 200),
+    (9, generate::day09, 100),
+    (10, generate::day10, 100),
+    (11, generate::day11, 10),
+    (13, generate::day13, 800),
+];
+
+/// Every day that can be drawn, in order
+pub const VISUALS: &[visual::Entry] = &[
+    visual::entry::<day04::Day04>(4),
+    visual::entry::<day05::Day05>(5),
+    visual::entry::<day09::Day09>(9),
+    visual::entry::<day11::Day11>(11),
+    visual::entry::<day13::Day13>(13),
+];
+
+/// Every day that can be explored, in order
+pub const REPLS: &[repl::Entry] = &[
+    (4, session::<day04::Day04>),
+    (9, session::<day09::Day09>),
+    (11, session::<day11::Day11>),
+    (13, session::<day13::Day13>),
+];
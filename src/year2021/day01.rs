@@ -0,0 +1,78 @@
+use std::io::BufRead;
+
+use crate::input::{read_lines, ReadError};
+use crate::parse::{parse_lines, parse_number};
+use crate::{Answer, Params, ParseError, Solution};
+
+pub fn parse_input<T: AsRef<str>>(input: T) -> Result<Vec<u32>, ParseError> {
+    parse_lines(input.as_ref(), |line| parse_number(line, line))
+}
+
+// Part 1
+pub fn count_increments(numbers: &[u32]) -> usize {
+    numbers.windows(2).filter(|pair| pair[1] > pair[0]).count()
+}
+
+// Part 2
+pub fn count_increments_triplets(numbers: &[u32]) -> usize {
+    numbers.windows(4).filter(|col| col[3] > col[0]).count()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn read(reader: &mut dyn BufRead) -> Result<Self::Input, ReadError> {
+        read_lines(reader, |line| parse_number(line, line))
+    }
+
+    fn part1(numbers: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        Ok(count_increments(numbers).into())
+    }
+
+    fn part2(numbers: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        Ok(count_increments_triplets(numbers).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day01/example1.txt");
+
+    #[test]
+    fn input_parses_correctly() {
+        println!("{}", TEST_INPUT);
+        let numbers = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(
+            numbers,
+            vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263]
+        );
+    }
+
+    #[test]
+    fn input_with_a_bad_line_fails_to_parse() {
+        let err = parse_input("199\n2OO\n208").unwrap_err();
+        assert_eq!(err, ParseError::new(1, "a number").offset_lines(1));
+    }
+
+    #[test]
+    fn counts_increments() {
+        let numbers = parse_input(TEST_INPUT).unwrap();
+        let increments = count_increments(&numbers);
+        assert_eq!(increments, 7);
+    }
+
+    #[test]
+    fn counts_increments_in_triplets() {
+        let numbers = parse_input(TEST_INPUT).unwrap();
+        let increments = count_increments_triplets(&numbers);
+        assert_eq!(increments, 5);
+    }
+}
@@ -0,0 +1,237 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::input::{read_lines, ReadError};
+use crate::parse::{parse_lines, parse_number};
+use crate::{Answer, Params, ParseError, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    Forward(u32),
+    Up(u32),
+    Down(u32),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Instruction, ParseError> {
+        let instruction = input.trim();
+
+        if let Some((direction, raw_amount)) = instruction.split_once(' ') {
+            let amount = parse_number(input, raw_amount)?;
+            match direction {
+                "forward" => Ok(Self::Forward(amount)),
+                "up" => Ok(Self::Up(amount)),
+                "down" => Ok(Self::Down(amount)),
+                _ => Err(ParseError::at(
+                    input,
+                    direction,
+                    "`forward`, `up` or `down`",
+                )),
+            }
+        } else {
+            Err(ParseError::at(
+                input,
+                instruction,
+                "a direction and an amount",
+            ))
+        }
+    }
+}
+
+/// Where the submarine is. The depth goes negative above the surface.
+#[derive(Default)]
+pub struct Submarine {
+    pub horizontal_pos: i128,
+    pub depth: i128,
+    pub aim: i128,
+}
+
+impl Submarine {
+    pub fn new() -> Self {
+        Submarine {
+            horizontal_pos: 0,
+            depth: 0,
+            aim: 0,
+        }
+    }
+
+    /// Moves following the first reading of the manual, where `up` and `down` change the depth
+    pub fn mv_without_aim(&mut self, instruction: Instruction) -> Result<(), String> {
+        match instruction {
+            Instruction::Forward(amount) => {
+                self.horizontal_pos = too_far(self.horizontal_pos.checked_add(amount.into()))?
+            }
+            Instruction::Down(amount) => {
+                self.depth = too_far(self.depth.checked_add(amount.into()))?
+            }
+            Instruction::Up(amount) => self.depth = too_far(self.depth.checked_sub(amount.into()))?,
+        }
+        Ok(())
+    }
+
+    pub fn mv(&mut self, instruction: Instruction) -> Result<(), String> {
+        match instruction {
+            Instruction::Forward(amount) => {
+                self.horizontal_pos = too_far(self.horizontal_pos.checked_add(amount.into()))?;
+                let dive = self.aim.checked_mul(amount.into());
+                self.depth = too_far(dive.and_then(|dive| self.depth.checked_add(dive)))?;
+            }
+            Instruction::Down(amount) => self.aim = too_far(self.aim.checked_add(amount.into()))?,
+            Instruction::Up(amount) => self.aim = too_far(self.aim.checked_sub(amount.into()))?,
+        }
+        Ok(())
+    }
+
+    /// The horizontal position multiplied by the depth
+    pub fn answer(&self) -> Result<Answer, String> {
+        let product = self.horizontal_pos.checked_mul(self.depth);
+        too_far(product)?.try_into()
+    }
+}
+
+fn too_far(position: Option<i128>) -> Result<i128, String> {
+    position.ok_or_else(|| "the submarine goes too far to keep track of".to_owned())
+}
+
+/// Parses the planned course
+pub fn parse_input<T: AsRef<str>>(input: T) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(input.as_ref(), str::parse)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn read(reader: &mut dyn BufRead) -> Result<Self::Input, ReadError> {
+        read_lines(reader, str::parse)
+    }
+
+    fn part1(instructions: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        let mut sub = Submarine::new();
+
+        for instruction in instructions {
+            sub.mv_without_aim(*instruction)?;
+        }
+
+        sub.answer()
+    }
+
+    fn part2(instructions: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        let mut sub = Submarine::new();
+
+        for instruction in instructions {
+            sub.mv(*instruction)?;
+        }
+
+        sub.answer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn direction_from_string() {
+        assert_eq!("up 5".parse(), Ok(Instruction::Up(5)));
+        assert_eq!("down 2".parse(), Ok(Instruction::Down(2)));
+        assert_eq!("forward 3".parse(), Ok(Instruction::Forward(3)));
+    }
+
+    #[test]
+    fn direction_from_random_string_fails() {
+        assert_eq!(
+            "foobar".parse::<Instruction>(),
+            Err(ParseError::new(1, "a direction and an amount"))
+        );
+        assert_eq!(
+            "backward 3".parse::<Instruction>(),
+            Err(ParseError::new(1, "`forward`, `up` or `down`"))
+        );
+        assert_eq!(
+            "up five".parse::<Instruction>(),
+            Err(ParseError::new(4, "a number"))
+        );
+    }
+
+    #[test]
+    fn it_solves_long_courses() {
+        let params = Params::default();
+
+        let course = parse_input("forward 4294967295\ndown 4294967295").unwrap();
+        assert_eq!(
+            Day02::part1(&course, &params),
+            Ok(Answer::Number(18446744065119617025))
+        );
+
+        // The biggest moves a command takes put the position times the depth past 64 bits
+        let course = parse_input("forward 4294967295\n".repeat(4) + "down 4294967295").unwrap();
+        assert_eq!(
+            Day02::part1(&course, &params),
+            Err("the answer 73786976260478468100 does not fit in 64 bits".to_owned())
+        );
+        let course = parse_input("down 4294967295\nforward 4294967295\n".repeat(3)).unwrap();
+        assert_eq!(
+            Day02::part2(&course, &params),
+            Err("the answer 1426106924260633896935403552750 does not fit in 64 bits".to_owned())
+        );
+
+        // Courses may take the submarine above the surface, and then there's no answer
+        let course = parse_input("forward 3\nup 4").unwrap();
+        assert_eq!(
+            Day02::part1(&course, &params),
+            Err("the answer -12 is negative".to_owned())
+        );
+    }
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day02/example1.txt");
+
+    #[test]
+    fn parses_input() {
+        let instructions = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(
+            instructions,
+            vec![
+                Instruction::Forward(5),
+                Instruction::Down(5),
+                Instruction::Forward(8),
+                Instruction::Up(3),
+                Instruction::Down(8),
+                Instruction::Forward(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn moves_the_submarine_without_aim() {
+        let instructions = parse_input(TEST_INPUT).unwrap();
+        let mut submarine = Submarine::new();
+
+        for instruction in instructions {
+            submarine.mv_without_aim(instruction).unwrap();
+        }
+
+        assert_eq!(submarine.horizontal_pos, 15);
+        assert_eq!(submarine.depth, 10);
+    }
+
+    #[test]
+    fn moves_the_submarine() {
+        let instructions = parse_input(TEST_INPUT).unwrap();
+        let mut submarine = Submarine::new();
+
+        for instruction in instructions {
+            submarine.mv(instruction).unwrap();
+        }
+
+        assert_eq!(submarine.horizontal_pos, 15);
+        assert_eq!(submarine.depth, 60);
+    }
+}
@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use crate::parse::parse_lines;
+use crate::{Answer, Params, ParseError, Solution};
+
+pub struct Telemetry {
+    entries: Vec<String>,
+    entry_len: usize,
+}
+
+impl FromStr for Telemetry {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let entry_len = input.lines().next().map_or(0, str::len);
+
+        if entry_len == 0 {
+            return Err(ParseError::new(1, "a binary digit"));
+        }
+
+        let entries = parse_lines(input, |line| {
+            if let Some((col, _)) = line.char_indices().find(|(_, c)| *c != '0' && *c != '1') {
+                return Err(ParseError::new(col + 1, "a binary digit"));
+            }
+
+            if line.len() != entry_len {
+                return Err(ParseError::new(
+                    line.len().min(entry_len) + 1,
+                    format!("an entry of {} bits", entry_len),
+                ));
+            }
+
+            Ok(line.to_owned())
+        })?;
+
+        Ok(Telemetry { entries, entry_len })
+    }
+}
+
+impl Telemetry {
+    pub fn consumption(&self) -> Result<u128, String> {
+        let len = self.entries.len();
+
+        let mut ones_per_position: Vec<usize> = vec![0; self.entry_len];
+        for entry in &self.entries {
+            for (pos, bit) in entry.chars().enumerate() {
+                if bit == '1' {
+                    *(ones_per_position.get_mut(pos).unwrap()) += 1;
+                }
+            }
+        }
+
+        // `gamma` is the most common bit per position, and `epsilon` the least common one
+        let (gamma, epsilon): (String, String) = ones_per_position
+            .into_iter()
+            .map(|no_ones| {
+                if no_ones > len / 2 {
+                    ('1', '0')
+                } else {
+                    ('0', '1')
+                }
+            })
+            .unzip();
+
+        Ok(rate(&gamma)? * rate(&epsilon)?)
+    }
+
+    pub fn life_support(&self) -> Result<u128, String> {
+        let o2 = find_entry(&self.entries, self.entry_len, |zeroes, ones| ones >= zeroes);
+        let co2 = find_entry(&self.entries, self.entry_len, |zeroes, ones| zeroes > ones);
+
+        Ok(rate(&o2)? * rate(&co2)?)
+    }
+}
+
+/// Reads a rate from its bits. Two rates of 64 bits at most always multiply within 128 bits.
+fn rate(bits: &str) -> Result<u128, String> {
+    (u64::from_str_radix(bits, 2))
+        .map(u128::from)
+        .map_err(|_| format!("the rate {} does not fit in 64 bits", bits))
+}
+
+fn find_entry<F: Fn(usize, usize) -> bool>(
+    entries: &[String],
+    entry_len: usize,
+    bit_comparator: F,
+) -> String {
+    let mut entries = entries.to_vec();
+    for pos in 0..entry_len {
+        let (entries_with_zero, entries_with_one): (Vec<_>, Vec<_>) = entries
+            .into_iter()
+            .partition(|entry| entry.chars().nth(pos).unwrap() == '0');
+
+        // A bit no entry has is never the one to keep, even if it's the least common
+        let keep_ones = bit_comparator(entries_with_zero.len(), entries_with_one.len());
+        entries = if (keep_ones && !entries_with_one.is_empty()) || entries_with_zero.is_empty() {
+            entries_with_one
+        } else {
+            entries_with_zero
+        };
+
+        if entries.len() == 1 {
+            break;
+        }
+    }
+
+    entries.pop().unwrap()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Telemetry;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part1(telemetry: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        telemetry.consumption()?.try_into()
+    }
+
+    fn part2(telemetry: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        telemetry.life_support()?.try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day03/example1.txt");
+    #[test]
+    fn it_parses_the_input() {
+        let telemetry: Telemetry = TEST_INPUT.parse().unwrap();
+
+        assert_eq!(telemetry.entry_len, 5);
+        assert_eq!(
+            telemetry.entries,
+            vec![
+                "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000",
+                "11001", "00010", "01010"
+            ]
+        )
+    }
+
+    #[test]
+    fn it_fails_to_parse_uneven_entries() {
+        let err = "00100\n1111\n10110".parse::<Telemetry>().err();
+        assert_eq!(
+            err,
+            Some(ParseError::new(5, "an entry of 5 bits").offset_lines(1))
+        );
+
+        let err = "00100\n11210".parse::<Telemetry>().err();
+        assert_eq!(
+            err,
+            Some(ParseError::new(3, "a binary digit").offset_lines(1))
+        );
+    }
+
+    #[test]
+    fn it_fails_to_parse_empty_entries() {
+        assert_eq!(
+            "".parse::<Telemetry>().err(),
+            Some(ParseError::new(1, "a binary digit"))
+        );
+        assert_eq!(
+            "\n\n".parse::<Telemetry>().err(),
+            Some(ParseError::new(1, "a binary digit"))
+        );
+    }
+
+    #[test]
+    fn it_fails_to_solve_answers_too_big() {
+        let params = Params::default();
+
+        let widest: Telemetry = "1".repeat(32).parse().unwrap();
+        assert_eq!(
+            Day03::part2(&widest, &params),
+            Ok(Answer::Number(u64::from(u32::MAX).pow(2)))
+        );
+
+        // 33 bits wide, both ratings are past 32 bits and their product past 64
+        let wider: Telemetry = "1".repeat(33).parse().unwrap();
+        assert_eq!(
+            Day03::part2(&wider, &params),
+            Err("the answer 73786976277658337281 does not fit in 64 bits".to_owned())
+        );
+
+        let too_wide: Telemetry = format!("1{}", "0".repeat(64)).parse().unwrap();
+        assert_eq!(
+            too_wide.life_support(),
+            Err(format!(
+                "the rate 1{} does not fit in 64 bits",
+                "0".repeat(64)
+            ))
+        );
+    }
+
+    #[test]
+    fn it_finds_ratings_among_repeated_entries() {
+        let telemetry: Telemetry = "101\n101\n101".parse().unwrap();
+        assert_eq!(telemetry.life_support(), Ok(25));
+    }
+
+    #[test]
+    fn it_calcualtes_consumption() {
+        let telemetry: Telemetry = TEST_INPUT.parse().unwrap();
+        assert_eq!(telemetry.consumption(), Ok(198));
+    }
+
+    #[test]
+    fn it_calculates_life_support() {
+        let telemetry: Telemetry = TEST_INPUT.parse().unwrap();
+        assert_eq!(telemetry.life_support(), Ok(230));
+    }
+}
@@ -0,0 +1,618 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::input::{read_sections, sections, ReadError, Section};
+use crate::parse::{parse_lines, parse_number};
+use crate::repl::{arg, Command, Explore};
+use crate::visual::{Colour, Recorder, Visualize};
+use crate::{Answer, Params, ParseError, Solution};
+
+#[derive(Clone)]
+pub struct BingoGame {
+    draws: Vec<u32>,
+    boards: Vec<Board>,
+}
+
+impl FromStr for BingoGame {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<BingoGame, ParseError> {
+        BingoGame::from_sections(sections(input).map(Ok))
+    }
+}
+
+impl BingoGame {
+    /// Parses a game from the sections of its input, be they split from a string or read one by
+    /// one
+    fn from_sections<T, E>(
+        mut sections: impl Iterator<Item = Result<Section<T>, E>>,
+    ) -> Result<BingoGame, E>
+    where
+        T: AsRef<str>,
+        E: From<ParseError>,
+    {
+        // First section is the line with the number draws
+        let first = match sections.next().transpose()? {
+            Some(first) => first,
+            None => {
+                return Ok(BingoGame {
+                    draws: vec![],
+                    boards: vec![],
+                })
+            }
+        };
+        let mut lines = first.text.as_ref().lines();
+        let first_line = lines.next().unwrap();
+        let draws: Vec<u32> = first_line
+            .split(',')
+            .map(|n| parse_number(first_line, n))
+            .collect::<Result<_, _>>()
+            .map_err(|err| err.offset_lines(first.line))?;
+
+        if lines.next().is_some() {
+            return Err(ParseError::new(1, "an empty line")
+                .offset_lines(first.line + 1)
+                .into());
+        }
+
+        // Every other section is a board
+        let boards = sections
+            .map(|section| {
+                let section = section?;
+                parse_board(section.text.as_ref())
+                    .map_err(|err| err.offset_lines(section.line).into())
+            })
+            .collect::<Result<_, E>>()?;
+
+        Ok(BingoGame { draws, boards })
+    }
+}
+
+/// Parses the rows of a board, which need the same amount of numbers to be able to check columns
+fn parse_board(input: &str) -> Result<Board, ParseError> {
+    let mut size = None;
+
+    let rows = parse_lines(input, |line| {
+        let row: Vec<u32> = line
+            .split_whitespace()
+            .map(|number| parse_number(line, number))
+            .collect::<Result<_, _>>()?;
+
+        let size = *size.get_or_insert(row.len());
+        if row.len() != size {
+            return Err(ParseError::new(
+                line.len() + 1,
+                format!("a row of {} numbers", size),
+            ));
+        }
+
+        Ok(row)
+    })?;
+
+    Ok(Board::new(rows))
+}
+
+impl BingoGame {
+    pub fn play(&mut self) -> (Option<Board>, u32) {
+        for number in &self.draws {
+            for board in &mut self.boards {
+                board.mark(*number);
+                if board.is_winner() {
+                    // FIXME figure out how to return a reference that lives long enough instead of cloning.
+                    return (Some(board.clone()), *number);
+                }
+            }
+        }
+        (None, 0)
+    }
+
+    pub fn play_last(&mut self) -> (Option<Board>, u32) {
+        let mut winning_boards: Vec<usize> = Vec::with_capacity(self.boards.len());
+        let mut boards = self.boards.clone();
+        let board_count = self.boards.len();
+
+        for number in &self.draws {
+            for (i, board) in boards.iter_mut().enumerate() {
+                // FIXME ??
+                if winning_boards.contains(&i) {
+                    continue;
+                };
+
+                board.mark(*number);
+
+                if board.is_winner() {
+                    winning_boards.push(i);
+                }
+
+                if winning_boards.len() == board_count {
+                    return (Some(board.clone()), *number);
+                }
+            }
+        }
+
+        if !winning_boards.is_empty() {
+            let cloned_board = self
+                .boards
+                .get(*winning_boards.last().unwrap())
+                .unwrap()
+                .clone();
+
+            (Some(cloned_board), *self.draws.last().unwrap())
+        } else {
+            (None, 0)
+        }
+    }
+}
+
+/// Most boards drawn side by side before starting a new row of boards
+const BOARDS_PER_ROW: usize = 10;
+
+/// Unmarked numbers are grey and marked ones yellow, turning green once their board wins
+impl Visualize for Day04 {
+    const COLOURS: &'static [Colour] = &[
+        [16, 16, 24],
+        [72, 72, 84],
+        [236, 196, 64],
+        [36, 84, 52],
+        [80, 210, 110],
+    ];
+
+    /// A frame per number drawn, until every board wins
+    fn record(
+        bingo_game: &Self::Input,
+        _params: &Params,
+        recorder: &mut Recorder,
+    ) -> Result<(), String> {
+        let mut boards = bingo_game.boards.clone();
+        let side = boards
+            .iter()
+            .map(|board| board.rows.len().max(board.rows[0].len()))
+            .max()
+            .unwrap_or(0);
+        let columns = boards.len().min(BOARDS_PER_ROW);
+        let rows = boards.len().div_ceil(BOARDS_PER_ROW);
+
+        // Boards are one cell apart
+        let draw = |boards: &[Board]| {
+            let mut frame = Grid::new(columns * (side + 1), rows * (side + 1), 0);
+            for (i, board) in boards.iter().enumerate() {
+                let corner = Point::new(
+                    (i % BOARDS_PER_ROW * (side + 1)) as i64,
+                    (i / BOARDS_PER_ROW * (side + 1)) as i64,
+                );
+                let won = if board.is_winner() { 2 } else { 0 };
+                for (y, row) in board.rows.iter().enumerate() {
+                    for (x, MarkedNumber(_, marked)) in row.iter().enumerate() {
+                        frame[corner + Point::new(x as i64, y as i64)] = 1 + *marked as u8 + won;
+                    }
+                }
+            }
+            frame
+        };
+
+        recorder.record(draw(&boards))?;
+        for &number in &bingo_game.draws {
+            if boards.iter().all(Board::is_winner) {
+                break;
+            }
+            for board in boards.iter_mut().filter(|board| !board.is_winner()) {
+                board.mark(number);
+            }
+            recorder.record(draw(&boards))?;
+        }
+        Ok(())
+    }
+}
+
+/// A game played a number at a time in `aoc repl`, and the boards that won so far, in order
+pub struct BingoHall {
+    game: BingoGame,
+    drawn: usize,
+    winners: Vec<usize>,
+}
+
+impl BingoHall {
+    /// Marks `number` on every board, returning a line for every board it makes win
+    fn mark(&mut self, number: u32) -> Vec<String> {
+        let mut wins = vec![];
+        for (i, board) in self.game.boards.iter_mut().enumerate() {
+            board.mark(number);
+            if board.is_winner() && !self.winners.contains(&i) {
+                self.winners.push(i);
+                wins.push(format!(
+                    "Board {} wins, scoring {} × {} = {}",
+                    i + 1,
+                    board.score(),
+                    number,
+                    final_score(Some(board), number)
+                ));
+            }
+        }
+        wins
+    }
+
+    fn board(&self, args: &[&str]) -> Result<&Board, String> {
+        let number: usize = arg(args, 0, None, "a board number")?;
+        (number.checked_sub(1))
+            .and_then(|i| self.game.boards.get(i))
+            .ok_or_else(|| format!("no board {}, there are {}", number, self.game.boards.len()))
+    }
+}
+
+impl Explore for Day04 {
+    type State = BingoHall;
+
+    const COMMANDS: &'static [Command] = &[
+        ("draw", "[n]", "Draw the next n numbers, 1 by default"),
+        (
+            "mark",
+            "<number>",
+            "Mark a number on every board without drawing it",
+        ),
+        (
+            "show",
+            "[board]",
+            "Show the numbers drawn and the winners, or a board",
+        ),
+    ];
+
+    fn start(bingo_game: &BingoGame) -> BingoHall {
+        BingoHall {
+            game: bingo_game.clone(),
+            drawn: 0,
+            winners: vec![],
+        }
+    }
+
+    fn run(hall: &mut BingoHall, name: &str, args: &[&str]) -> Result<String, String> {
+        match name {
+            "draw" => {
+                let count: usize = arg(args, 0, Some(1), "how many numbers to draw")?;
+                let left = hall.game.draws.len() - hall.drawn;
+                if left == 0 {
+                    return Err("every number was drawn already".to_owned());
+                }
+
+                let draws = hall.game.draws[hall.drawn..][..count.min(left)].to_vec();
+                hall.drawn += draws.len();
+                let wins: Vec<String> = draws.iter().flat_map(|&n| hall.mark(n)).collect();
+                let drew = draws.iter().map(u32::to_string).collect::<Vec<_>>();
+
+                Ok([format!("Drew {}", drew.join(", "))]
+                    .into_iter()
+                    .chain(wins)
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            "mark" => {
+                let number = arg(args, 0, None, "a number")?;
+                Ok(hall.mark(number).join("\n"))
+            }
+            "show" if args.is_empty() => {
+                let drawn: Vec<String> = hall.game.draws[..hall.drawn]
+                    .iter()
+                    .map(u32::to_string)
+                    .collect();
+                let winners: Vec<String> =
+                    hall.winners.iter().map(|i| (i + 1).to_string()).collect();
+                Ok(format!(
+                    "Drawn {} of {}{}\nWinning boards, in order: {}",
+                    drawn.len(),
+                    hall.game.draws.len(),
+                    if drawn.is_empty() {
+                        String::new()
+                    } else {
+                        format!(": {}", drawn.join(","))
+                    },
+                    if winners.is_empty() {
+                        "none".to_owned()
+                    } else {
+                        winners.join(", ")
+                    }
+                ))
+            }
+            "show" => {
+                // Marked numbers are in brackets
+                let board = hall.board(args)?;
+                Ok((board.rows.iter())
+                    .map(|row| {
+                        row.iter()
+                            .map(|MarkedNumber(n, marked)| {
+                                if *marked {
+                                    format!("{:>3}]", format!("[{}", n))
+                                } else {
+                                    format!("{:>3} ", n)
+                                }
+                            })
+                            .collect::<String>()
+                            .trim_end()
+                            .to_owned()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            _ => Err(format!("command `{}` is not handled", name)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct MarkedNumber(u32, bool);
+
+impl MarkedNumber {
+    pub fn new(n: u32) -> Self {
+        MarkedNumber(n, false)
+    }
+    fn mark(&mut self) {
+        self.1 = true;
+    }
+}
+
+impl From<u32> for MarkedNumber {
+    fn from(n: u32) -> Self {
+        MarkedNumber::new(n)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Board {
+    rows: Vec<Vec<MarkedNumber>>,
+}
+
+impl Board {
+    pub fn new(rows: Vec<Vec<u32>>) -> Self {
+        Board {
+            rows: rows
+                .into_iter()
+                .map(|row| row.into_iter().map(|n| n.into()).collect())
+                .collect(),
+        }
+    }
+
+    pub fn mark(&mut self, drawn_number: u32) {
+        for row in &mut self.rows {
+            if let Some(marked_number) =
+                row.iter_mut()
+                    .find_map(|n| if n.0 == drawn_number { Some(n) } else { None })
+            {
+                (*marked_number).mark();
+                break;
+            }
+        }
+    }
+
+    pub fn is_winner(&self) -> bool {
+        let width = self.rows.first().map_or(0, Vec::len);
+
+        self.rows.iter().any(|row| row.iter().all(|n| n.1))
+            || (0..width).any(|i| self.rows.iter().all(|row| row[i].1))
+    }
+
+    /// The sum of the unmarked numbers, which fits in 64 bits for boards of up to 2³² numbers
+    pub fn score(&self) -> u64 {
+        self.rows
+            .iter()
+            .flatten()
+            .filter_map(|MarkedNumber(n, marked)| if *marked { None } else { Some(n) })
+            .map(|&n| u64::from(n))
+            .sum()
+    }
+}
+
+/// The score of a board times the number that made it win, 0 if no board won
+fn final_score(board: Option<&Board>, last_number: u32) -> u128 {
+    board.map_or(0, |board| {
+        u128::from(board.score()) * u128::from(last_number)
+    })
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = BingoGame;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn read(reader: &mut dyn BufRead) -> Result<Self::Input, ReadError> {
+        BingoGame::from_sections(
+            read_sections(reader).map(|section| section.map_err(ReadError::from)),
+        )
+    }
+
+    fn part1(bingo_game: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        let (winning_board, last_number) = bingo_game.clone().play();
+        final_score(winning_board.as_ref(), last_number).try_into()
+    }
+
+    fn part2(bingo_game: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        let (winning_board, last_number) = bingo_game.clone().play_last();
+        final_score(winning_board.as_ref(), last_number).try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day04/example1.txt");
+
+    #[test]
+    fn it_parses_game_input() {
+        let bingo_game = TEST_INPUT.parse::<BingoGame>().unwrap();
+
+        assert_eq!(
+            bingo_game.draws,
+            vec![
+                7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24, 10, 16, 13, 6, 15, 25, 12, 22, 18, 20, 8,
+                19, 3, 26, 1
+            ]
+        );
+        assert_eq!(
+            bingo_game.boards,
+            vec![
+                Board::new(vec![
+                    vec![22, 13, 17, 11, 0],
+                    vec![8, 2, 23, 4, 24],
+                    vec![21, 9, 14, 16, 7],
+                    vec![6, 10, 3, 18, 5],
+                    vec![1, 12, 20, 15, 19],
+                ]),
+                Board::new(vec![
+                    vec![3, 15, 0, 2, 22],
+                    vec![9, 18, 13, 17, 5],
+                    vec![19, 8, 7, 25, 23],
+                    vec![20, 11, 10, 24, 4],
+                    vec![14, 21, 16, 12, 6],
+                ]),
+                Board::new(vec![
+                    vec![14, 21, 17, 24, 4],
+                    vec![10, 16, 15, 9, 19],
+                    vec![18, 8, 23, 26, 20],
+                    vec![22, 11, 13, 6, 5],
+                    vec![2, 0, 12, 3, 7],
+                ])
+            ]
+        );
+    }
+
+    #[test]
+    fn it_fails_to_parse_bad_boards() {
+        let err = "7,4,9\n\n22 13 17\n 8  2".parse::<BingoGame>().err();
+        assert_eq!(
+            err,
+            Some(ParseError::new(6, "a row of 3 numbers").offset_lines(3))
+        );
+
+        let err = "7,4,9\n\n22 13 17\n 8  x 23".parse::<BingoGame>().err();
+        assert_eq!(err, Some(ParseError::new(5, "a number").offset_lines(3)));
+    }
+
+    #[test]
+    fn it_fails_to_solve_scores_too_big() {
+        let params = Params::default();
+
+        // Numbers near 2^32 score just under 64 bits with one unmarked, and past them with two
+        let game: BingoGame = "4294967295\n\n4294967295\n4294967294".parse().unwrap();
+        assert_eq!(
+            Day04::part1(&game, &params),
+            Ok(Answer::Number(18446744060824649730))
+        );
+
+        let game: BingoGame = "4294967295\n\n4294967295\n4294967294\n4294967294"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            Day04::part1(&game, &params),
+            Err("the answer 36893488121649299460 does not fit in 64 bits".to_owned())
+        );
+    }
+
+    #[test]
+    fn it_checks_every_column_of_boards_that_are_not_square() {
+        let mut tall = Board::new(vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+        tall.mark(1);
+        tall.mark(4);
+        assert!(!tall.is_winner());
+        tall.mark(6);
+        tall.mark(2);
+        assert!(tall.is_winner());
+
+        let mut wide = Board::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        wide.mark(3);
+        wide.mark(6);
+        assert!(wide.is_winner());
+    }
+
+    #[test]
+    fn it_marks_numbers_in_board() {
+        let mut board = Board::new(vec![vec![1, 2, 3]]);
+
+        board.mark(1);
+        assert_eq!(
+            board.rows,
+            vec![vec![
+                MarkedNumber(1, true),
+                MarkedNumber(2, false),
+                MarkedNumber(3, false)
+            ]]
+        )
+    }
+
+    #[test]
+    fn it_marks_board_as_winner_when_row_is_full() {
+        let mut board = Board::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert!(!board.is_winner());
+        board.mark(1);
+        assert!(!board.is_winner());
+        board.mark(2);
+        board.mark(3);
+        assert!(board.is_winner());
+    }
+
+    #[test]
+    fn it_marks_board_as_winner_when_column_is_full() {
+        let mut board = Board::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert!(!board.is_winner());
+        board.mark(1);
+        board.mark(4);
+        assert!(board.is_winner());
+    }
+
+    #[test]
+    fn it_plays_until_a_board_wins() {
+        let mut bingo_game = TEST_INPUT.parse::<BingoGame>().unwrap();
+
+        let (winning_board, last_number) = bingo_game.play();
+        assert_eq!(last_number, 24);
+        assert_eq!(winning_board.unwrap().score(), 188);
+    }
+
+    #[test]
+    fn it_records_a_frame_per_draw_until_every_board_wins() {
+        let bingo_game = TEST_INPUT.parse::<BingoGame>().unwrap();
+        let mut recorder = Recorder::new();
+        Day04::record(&bingo_game, &Params::default(), &mut recorder).unwrap();
+
+        // The last board wins with 13, the 15th number drawn
+        assert_eq!(recorder.frames().len(), 1 + 15);
+        let last = recorder.frames().last().unwrap();
+        assert_eq!((last.width(), last.height()), (18, 6));
+        assert!(last.cells().all(|(_, &level)| level != 1 && level != 2));
+    }
+
+    #[test]
+    fn it_plays_until_all_boards_win() {
+        let mut bingo_game = TEST_INPUT.parse::<BingoGame>().unwrap();
+
+        let (last_winning_board, winning_number) = bingo_game.play_last();
+        assert_eq!(winning_number, 13);
+        assert_eq!(last_winning_board.unwrap().score(), 148);
+    }
+
+    #[test]
+    fn it_draws_numbers_in_the_repl() {
+        let mut hall = Day04::start(&TEST_INPUT.parse().unwrap());
+
+        assert_eq!(
+            Day04::run(&mut hall, "draw", &["12"]),
+            Ok("Drew 7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24\n\
+                Board 3 wins, scoring 188 × 24 = 4512"
+                .to_owned())
+        );
+        assert_eq!(
+            Day04::run(&mut hall, "show", &["3"])
+                .unwrap()
+                .lines()
+                .next(),
+            Some("[14][21][17][24] [4]")
+        );
+        assert_eq!(
+            Day04::run(&mut hall, "show", &["4"]),
+            Err("no board 4, there are 3".to_owned())
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::input::{read_lines, ReadError};
+use crate::parse::{parse_lines, parse_number};
+use crate::visual::{check_size, Colour, Recorder, Visualize};
+use crate::{Answer, Params, ParseError, Solution};
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = VentField;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn read(reader: &mut dyn BufRead) -> Result<Self::Input, ReadError> {
+        Ok(VentField::new(read_lines(reader, str::parse)?))
+    }
+
+    fn part1(vent_field: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        vent_field.straight_overlaps().map(Answer::from)
+    }
+
+    fn part2(vent_field: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        vent_field.overlaps().map(Answer::from)
+    }
+}
+
+pub struct VentField {
+    lines: Vec<Line>,
+}
+
+impl FromStr for VentField {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let lines: Vec<Line> = parse_lines(input, str::parse)?;
+        Ok(VentField::new(lines))
+    }
+}
+
+impl VentField {
+    pub fn new(lines: Vec<Line>) -> Self {
+        VentField { lines }
+    }
+
+    /// Points where at least two lines of vents overlap. Fails if the lines spread over too big an
+    /// area to count on.
+    pub fn overlaps(&self) -> Result<usize, String> {
+        self.count_overlaps(|_| true)
+    }
+
+    /// Like `overlaps`, but only considering horizontal and vertical lines
+    pub fn straight_overlaps(&self) -> Result<usize, String> {
+        self.count_overlaps(|line| line.is_horizontal() || line.is_vertical())
+    }
+
+    fn count_overlaps<F: Fn(&Line) -> bool>(&self, filter: F) -> Result<usize, String> {
+        let lines: Vec<&Line> = self
+            .lines
+            .iter()
+            .filter(|line| filter(line) && line.point_count() > 0)
+            .collect();
+
+        let (corner, width, height) = bounds(lines.iter().copied())?;
+        let mut field: Grid<u8> = Grid::new(width, height, 0);
+
+        for point in lines.iter().flat_map(|line| line.points()) {
+            let count = &mut field[point - corner];
+            *count = (*count + 1).min(2);
+        }
+
+        Ok(field.cells().filter(|(_, &count)| count > 1).count())
+    }
+}
+
+/// Most points the area around the lines of vents can have, so a few lines billions of points
+/// long are an error instead of taking all the memory
+const MAX_AREA: usize = 1 << 28;
+
+/// Top left corner, width and height of the smallest area holding all of `lines`, if it has at
+/// most `MAX_AREA` points
+fn bounds<'a>(lines: impl Iterator<Item = &'a Line>) -> Result<(Point, usize, usize), String> {
+    let mut points = lines.flat_map(|line| [line.0, line.1]);
+    let first = match points.next() {
+        Some(point) => point,
+        None => return Ok((Point::ORIGIN, 0, 0)),
+    };
+    let (min, max) = points.fold((first, first), |(min, max), point| {
+        (
+            Point::new(min.x.min(point.x), min.y.min(point.y)),
+            Point::new(max.x.max(point.x), max.y.max(point.y)),
+        )
+    });
+
+    let (width, height) = ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+    match width.checked_mul(height) {
+        Some(area) if area <= MAX_AREA => Ok((min, width, height)),
+        _ => Err(format!(
+            "the vents spread over {}×{} points, too many to count, the most is {}",
+            width, height, MAX_AREA
+        )),
+    }
+}
+
+/// Most frames to draw the vents in, so big inputs don't make huge animations
+const MAX_FRAMES: usize = 50;
+
+/// Empty ground is dark, a single vent is amber, and overlapping vents are red
+impl Visualize for Day05 {
+    const COLOURS: &'static [Colour] = &[[20, 20, 28], [240, 180, 40], [230, 40, 40]];
+
+    /// A frame every few lines of vents, laid out one after the other
+    fn record(
+        vent_field: &Self::Input,
+        _params: &Params,
+        recorder: &mut Recorder,
+    ) -> Result<(), String> {
+        let points = vent_field.lines.iter().flat_map(|line| [line.0, line.1]);
+        let width = points.clone().map(|point| point.x + 1).max().unwrap_or(0);
+        let height = points.map(|point| point.y + 1).max().unwrap_or(0);
+
+        check_size(width as usize, height as usize)?;
+
+        let mut frame = Grid::new(width as usize, height as usize, 0);
+        recorder.record(frame.clone())?;
+
+        let batch = vent_field.lines.len().div_ceil(MAX_FRAMES).max(1);
+        for lines in vent_field.lines.chunks(batch) {
+            for point in lines.iter().flat_map(Line::points) {
+                frame[point] = (frame[point] + 1).min(2);
+            }
+            recorder.record(frame.clone())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Line(pub Point, pub Point);
+
+impl Line {
+    pub fn is_horizontal(&self) -> bool {
+        self.0.x == self.1.x
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.0.y == self.1.y
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        let diff = self.0 - self.1;
+        diff.x.abs() == diff.y.abs()
+    }
+
+    /// How many points `points` walks through
+    pub fn point_count(&self) -> u64 {
+        if !self.is_vertical() && !self.is_horizontal() && !self.is_diagonal() {
+            return 0;
+        }
+
+        let diff = self.1 - self.0;
+        diff.x.unsigned_abs().max(diff.y.unsigned_abs()) + 1
+    }
+
+    pub fn points(&self) -> Points {
+        if !self.is_vertical() && !self.is_horizontal() && !self.is_diagonal() {
+            return Points::empty();
+        }
+
+        Points::from(self)
+    }
+}
+
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Line, ParseError> {
+        let pairs: Vec<&str> = line.split(" -> ").collect();
+
+        let (start, end) = match pairs[..] {
+            [a, b] => (
+                parse_point(a).map_err(|err| err.within(line, a))?,
+                parse_point(b).map_err(|err| err.within(line, b))?,
+            ),
+            _ => return Err(ParseError::new(1, "two points separated by ` -> `")),
+        };
+
+        Ok(Line(start, end))
+    }
+}
+
+pub struct Points {
+    next: Option<Point>,
+    end: Point,
+}
+
+impl Points {
+    fn empty() -> Self {
+        Points {
+            next: None,
+            end: Point::ORIGIN,
+        }
+    }
+}
+
+impl From<&Line> for Points {
+    fn from(line: &Line) -> Points {
+        Points {
+            next: Some(line.0),
+            end: line.1,
+        }
+    }
+}
+
+impl Iterator for Points {
+    type Item = Point;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next {
+            Some(current) if current == self.end => self.next.take(),
+            Some(current) => {
+                self.next = Some(current + (self.end - current).signum());
+                Some(current)
+            }
+            None => None,
+        }
+    }
+}
+
+fn parse_point(point: &str) -> Result<Point, ParseError> {
+    match point.split_once(',') {
+        Some((x, y)) => Ok(Point::new(
+            parse_number::<u32>(point, x.trim())?.into(),
+            parse_number::<u32>(point, y.trim())?.into(),
+        )),
+        None => Err(ParseError::new(1, "a point like `x,y`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::generate;
+    use crate::options::Options;
+    use crate::solution::solve;
+
+    fn p(x: i64, y: i64) -> Point {
+        Point::new(x, y)
+    }
+
+    #[test]
+    fn it_parses_points() {
+        assert_eq!(parse_point("0,1"), Ok(p(0, 1)))
+    }
+
+    #[test]
+    fn it_parses_lines() {
+        let line = "0,1 -> 0,3".parse();
+        assert_eq!(line, Ok(Line(p(0, 1), p(0, 3))));
+    }
+
+    #[test]
+    fn it_fails_to_parse_bad_lines() {
+        assert_eq!(
+            "0,1 => 0,3".parse::<Line>(),
+            Err(ParseError::new(1, "two points separated by ` -> `"))
+        );
+        assert_eq!(
+            "0,1 -> 0,y".parse::<Line>(),
+            Err(ParseError::new(10, "a number"))
+        );
+        assert_eq!(
+            "0,1 -> 03".parse::<Line>(),
+            Err(ParseError::new(8, "a point like `x,y`"))
+        );
+        assert_eq!(
+            "0,1 -> 0,4294967296".parse::<Line>(),
+            Err(ParseError::new(10, "a number"))
+        );
+    }
+
+    #[test]
+    fn it_detects_horizontal_lines() {
+        let line = Line(p(0, 0), p(0, 9));
+        assert!(line.is_horizontal());
+
+        let line = Line(p(9, 0), p(0, 0));
+        assert!(!line.is_horizontal());
+    }
+
+    #[test]
+    fn it_detects_vertical_lines() {
+        let line = Line(p(0, 0), p(0, 9));
+        assert!(!line.is_vertical());
+
+        let line = Line(p(9, 0), p(0, 0));
+        assert!(line.is_vertical());
+    }
+
+    #[test]
+    fn it_detects_diagonal_lines() {
+        let line = Line(p(0, 0), p(0, 9));
+        assert!(!line.is_diagonal());
+
+        let line = Line(p(0, 0), p(9, 9));
+        assert!(line.is_diagonal());
+
+        let line = Line(p(9, 9), p(0, 0));
+        assert!(line.is_diagonal());
+
+        let line = Line(p(9, 9), p(0, 1));
+        assert!(!line.is_diagonal());
+    }
+
+    #[test]
+    fn it_counts_the_points_of_lines() {
+        assert_eq!(Line(p(0, 1), p(0, 3)).point_count(), 3);
+        assert_eq!(Line(p(3, 1), p(1, 3)).point_count(), 3);
+        assert_eq!(Line(p(2, 2), p(2, 2)).point_count(), 1);
+        assert_eq!(Line(p(1, 0), p(3, 4)).point_count(), 0);
+    }
+
+    #[test]
+    fn it_correctly_makes_points() {
+        let line = Line(p(0, 1), p(0, 3));
+        let points: Vec<_> = line.points().collect();
+        assert_eq!(points, [p(0, 1), p(0, 2), p(0, 3)]);
+
+        let line = Line(p(1, 0), p(3, 0));
+        let points: Vec<_> = line.points().collect();
+        assert_eq!(points, [p(1, 0), p(2, 0), p(3, 0)]);
+
+        let line = Line(p(1, 0), p(2, 1));
+        let points: Vec<_> = line.points().collect();
+        assert_eq!(points, [p(1, 0), p(2, 1)]);
+
+        let line = Line(p(3, 1), p(1, 3));
+        let points: Vec<_> = line.points().collect();
+        assert_eq!(points, [p(3, 1), p(2, 2), p(1, 3)]);
+
+        let line = Line(p(1, 0), p(3, 4));
+        let points: Vec<_> = line.points().collect();
+        assert_eq!(points, []);
+    }
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day05/example1.txt");
+
+    #[test]
+    fn it_calculates_straight_line_overlaps() {
+        let vent_field = TEST_INPUT.parse::<VentField>().unwrap();
+        assert_eq!(vent_field.straight_overlaps(), Ok(5));
+    }
+
+    #[test]
+    fn it_calculates_line_overlaps() {
+        let vent_field = TEST_INPUT.parse::<VentField>().unwrap();
+        assert_eq!(vent_field.overlaps(), Ok(12));
+    }
+
+    #[test]
+    fn it_refuses_lines_spread_over_too_big_an_area() {
+        let vent_field = "0,0 -> 4294967295,0".parse::<VentField>().unwrap();
+        assert!(vent_field.straight_overlaps().is_err());
+        assert!(vent_field.overlaps().is_err());
+        let solved = solve::<Day05>("0,0 -> 4294967295,0", &Options::default()).unwrap();
+        assert!(solved.parts.iter().all(|part| part.answer.is_err()));
+
+        // Lines that aren't straight or diagonal are skipped, so they cover nothing
+        let vent_field = "0,0 -> 4294967295,1".parse::<VentField>().unwrap();
+        assert_eq!(vent_field.overlaps(), Ok(0));
+
+        // Only the area around the lines counts, not how far it is from the origin
+        let vent_field = "4294967290,7 -> 4294967295,7\n4294967295,2 -> 4294967295,9"
+            .parse::<VentField>()
+            .unwrap();
+        assert_eq!(vent_field.straight_overlaps(), Ok(1));
+    }
+
+    #[test]
+    fn it_counts_overlaps_of_many_long_lines() {
+        // More than 80 million points, all of them within the generator's 1000x1000 field
+        let input = generate(2021, 5, 300_000, 1).unwrap();
+        let vent_field = input.parse::<VentField>().unwrap();
+        assert!(vent_field.overlaps().is_ok());
+    }
+
+    #[test]
+    fn it_records_vents_overlapping() {
+        let vent_field = TEST_INPUT.parse::<VentField>().unwrap();
+        let mut recorder = Recorder::new();
+        Day05::record(&vent_field, &Params::default(), &mut recorder).unwrap();
+
+        assert_eq!(recorder.frames().len(), 1 + 10);
+        let last = recorder.frames().last().unwrap();
+        assert_eq!(last.cells().filter(|(_, &level)| level == 2).count(), 12);
+    }
+}
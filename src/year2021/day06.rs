@@ -0,0 +1,187 @@
+use std::str::FromStr;
+
+use crate::parse::parse_number;
+use crate::{Answer, Params, ParseError, Solution};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = FishSimulator;
+
+    const PARAMS: &'static [&'static str] = &["days"];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part1(simulator: &Self::Input, params: &Params) -> Result<Answer, String> {
+        let mut simulator = simulator.clone();
+        simulator.advance(params.get("days").unwrap_or(80))?;
+        simulator.count().map(Answer::from)
+    }
+
+    fn part2(simulator: &Self::Input, params: &Params) -> Result<Answer, String> {
+        let mut simulator = simulator.clone();
+        simulator.advance(params.get("days").unwrap_or(256))?;
+        simulator.count().map(Answer::from)
+    }
+}
+
+#[derive(Clone)]
+pub struct FishSimulator {
+    population: [u64; 9],
+}
+
+impl FishSimulator {
+    /// Lets `days` pass, or fails on the first day with too many fish to count. Every fish has
+    /// a child at least once a week, so that day comes within a few thousand days.
+    pub fn advance(&mut self, days: u64) -> Result<(), String> {
+        for day in 1..=days {
+            let new_fish = self.population[0];
+            self.population.copy_within(1.., 0);
+            self.population[8] = new_fish;
+            self.population[6] = (self.population[6].checked_add(new_fish))
+                .ok_or_else(|| format!("too many fish to count after {} days", day))?;
+        }
+        Ok(())
+    }
+
+    pub fn count(&self) -> Result<u64, String> {
+        (self.population.iter())
+            .try_fold(0_u64, |count, &fish| count.checked_add(fish))
+            .ok_or_else(|| "too many fish to count".to_owned())
+    }
+}
+
+impl FromStr for FishSimulator {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let input = input.trim_end();
+        let mut population = [0; 9];
+
+        for day_count in input.split(',').map(str::trim) {
+            let timer: usize = parse_number(input, day_count)?;
+            if timer >= population.len() {
+                return Err(ParseError::at(input, day_count, "a timer from 0 to 8"));
+            }
+            population[timer] += 1;
+        }
+
+        Ok(FishSimulator { population })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{join, Rng};
+    use crate::year2021::generate::number_lists;
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day06/example1.txt");
+
+    #[test]
+    fn it_counts_the_initial_population() {
+        let simulator = TEST_INPUT.parse::<FishSimulator>().unwrap();
+        assert_eq!(simulator.count(), Ok(5));
+    }
+
+    #[test]
+    fn it_fails_to_parse_bad_timers() {
+        let err = "3,4,9,1".parse::<FishSimulator>().err();
+        assert_eq!(err, Some(ParseError::new(5, "a timer from 0 to 8")));
+
+        let err = "3,4,,1".parse::<FishSimulator>().err();
+        assert_eq!(err, Some(ParseError::new(5, "a number")));
+    }
+
+    #[test]
+    fn it_counts_the_population_after_some_days_pass() {
+        let mut simulator = TEST_INPUT.parse::<FishSimulator>().unwrap();
+
+        simulator.advance(18).unwrap();
+        assert_eq!(simulator.count(), Ok(26));
+
+        simulator.advance(80 - 18).unwrap();
+        assert_eq!(simulator.count(), Ok(5934));
+    }
+
+    /// Number of fish after `days`, simulating every fish on its own
+    fn brute_force_count(timers: &[u8], days: u64) -> usize {
+        let mut fish = timers.to_vec();
+
+        for _ in 0..days {
+            let mut born = 0;
+            for timer in &mut fish {
+                if *timer == 0 {
+                    *timer = 6;
+                    born += 1;
+                } else {
+                    *timer -= 1;
+                }
+            }
+            fish.resize(fish.len() + born, 8);
+        }
+
+        fish.len()
+    }
+
+    #[test]
+    fn it_counts_like_simulating_every_fish() {
+        let mut rng = Rng::new(6);
+
+        // Any timer for the edge cases
+        let schools = number_lists(6, 6, |rng| {
+            let fish = rng.between(1, 6);
+            (0..fish).map(|_| rng.below(9)).collect()
+        });
+        for timers in schools {
+            let timers: Vec<u8> = timers.into_iter().map(|timer| timer as u8).collect();
+            let input = join(&timers, ",");
+            let days = rng.below(70);
+
+            let mut simulator = input.parse::<FishSimulator>().unwrap();
+            simulator.advance(days).unwrap();
+            assert_eq!(
+                simulator.count().unwrap() as usize,
+                brute_force_count(&timers, days),
+                "{} after {} days",
+                input,
+                days
+            );
+        }
+    }
+
+    #[test]
+    fn it_simulates_the_days_given() {
+        let simulator = Day06::parse(TEST_INPUT).unwrap();
+        let mut params = Params::default();
+        assert_eq!(Day06::part1(&simulator, &params), Ok(Answer::Number(5934)));
+
+        params.set("days", 18);
+        assert_eq!(Day06::part1(&simulator, &params), Ok(Answer::Number(26)));
+        assert_eq!(Day06::part2(&simulator, &params), Ok(Answer::Number(26)));
+    }
+
+    #[test]
+    fn it_fails_to_count_past_64_bits() {
+        let simulator = Day06::parse(TEST_INPUT).unwrap();
+        let mut params = Params::default();
+
+        params.set("days", 500);
+        assert_eq!(
+            Day06::part1(&simulator, &params),
+            Err("too many fish to count".to_owned())
+        );
+        params.set("days", 600);
+        assert_eq!(
+            Day06::part1(&simulator, &params),
+            Err("too many fish to count after 512 days".to_owned())
+        );
+        params.set("days", u64::MAX);
+        assert_eq!(
+            Day06::part2(&simulator, &params),
+            Err("too many fish to count after 512 days".to_owned())
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::str::FromStr;
+
+use crate::parse::parse_number;
+use crate::{Answer, Params, ParseError, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = CrabArmy;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part1(army: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        army.align().try_into()
+    }
+
+    fn part2(army: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        army.sim_align()?.try_into()
+    }
+}
+
+pub struct CrabArmy {
+    positions: Vec<u64>,
+}
+
+impl CrabArmy {
+    pub fn new(mut positions: Vec<u64>) -> Self {
+        positions.sort_unstable();
+        CrabArmy { positions }
+    }
+
+    /// Fuel needed to align when every step costs 1. The cheapest point is the median.
+    pub fn align(&self) -> u128 {
+        let median = self.positions[self.positions.len() / 2];
+
+        self.positions
+            .iter()
+            .map(|p| u128::from(p.abs_diff(median)))
+            .sum()
+    }
+
+    /// Fuel needed to align when every step costs 1 more than the last. The cheapest point is
+    /// within 1 of the average.
+    pub fn sim_align(&self) -> Result<u128, String> {
+        let sum: u128 = self.positions.iter().map(|&p| u128::from(p)).sum();
+        let avg_point = (sum / self.positions.len() as u128) as u64;
+
+        (avg_point.saturating_sub(1)..=avg_point.saturating_add(1))
+            .map(|avg| {
+                self.positions
+                    .iter()
+                    .try_fold(0_u128, |fuel, p| fuel.checked_add(seq_sum(p.abs_diff(avg))))
+                    .ok_or_else(|| "the fuel needed does not fit in 128 bits".to_owned())
+            })
+            .min()
+            .unwrap()
+    }
+}
+
+impl FromStr for CrabArmy {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let input = input.trim_end();
+        let positions: Vec<u64> = input
+            .split(',')
+            .map(|p| parse_number(input, p.trim()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(CrabArmy::new(positions))
+    }
+}
+
+/// Calculates the sum of 1 + 2 + ... + n
+fn seq_sum(n: u64) -> u128 {
+    let n = u128::from(n);
+    (n * (1 + n)) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year2021::generate::number_lists;
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day07/example1.txt");
+
+    #[test]
+    fn it_fails_to_parse_bad_positions() {
+        let err = "16,1,-2".parse::<CrabArmy>().err();
+        assert_eq!(err, Some(ParseError::new(6, "a number")));
+    }
+
+    #[test]
+    fn it_fails_to_solve_answers_too_big() {
+        let params = Params::default();
+
+        // Part 2 fuel grows with the square of the distance: it fits for crabs 2^32 apart, not 2^33
+        let army: CrabArmy = "16,1,4294967296".parse().unwrap();
+        assert_eq!(Day07::part1(&army, &params), Ok(Answer::Number(4294967295)));
+        assert_eq!(
+            Day07::part2(&army, &params),
+            Ok(Answer::Number(6148914669761680800))
+        );
+
+        let army: CrabArmy = "0,1,8589934592".parse().unwrap();
+        assert_eq!(Day07::part1(&army, &params), Ok(Answer::Number(8589934592)));
+        assert_eq!(
+            Day07::part2(&army, &params),
+            Err("the answer 24595658767809380352 does not fit in 64 bits".to_owned())
+        );
+    }
+
+    #[test]
+    fn it_calculates_constant_fuel_to_align() {
+        let army = TEST_INPUT.parse::<CrabArmy>().unwrap();
+        assert_eq!(army.align(), 37);
+    }
+
+    #[test]
+    fn it_calculates_fuel_to_align() {
+        let army = TEST_INPUT.parse::<CrabArmy>().unwrap();
+        assert_eq!(army.sim_align(), Ok(168));
+    }
+
+    /// Fuel to align on the cheapest point, trying every point between the outermost crabs
+    fn brute_force_align(positions: &[u64], cost: fn(u64) -> u64) -> u64 {
+        let (&min, &max) = (
+            positions.iter().min().unwrap(),
+            positions.iter().max().unwrap(),
+        );
+
+        (min..=max)
+            .map(|target| positions.iter().map(|&p| cost(p.abs_diff(target))).sum())
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn it_aligns_like_trying_every_point() {
+        // Small armies close to 0 for the edge cases
+        let armies = number_lists(7, 60, |rng| {
+            let crabs = rng.between(1, 8);
+            (0..crabs).map(|_| rng.below(12)).collect()
+        });
+        for positions in armies {
+            let army = CrabArmy::new(positions.clone());
+
+            let steps = |n| n;
+            assert_eq!(
+                army.align(),
+                brute_force_align(&positions, steps).into(),
+                "{:?}",
+                positions
+            );
+            let triangle = |n| n * (n + 1) / 2;
+            assert_eq!(
+                army.sim_align(),
+                Ok(brute_force_align(&positions, triangle).into()),
+                "{:?}",
+                positions
+            );
+        }
+    }
+}
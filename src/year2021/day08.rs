@@ -0,0 +1,463 @@
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::input::{read_lines, ReadError};
+use crate::parse::parse_lines;
+use crate::{Answer, Params, ParseError, Solution};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Decoder;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn read(reader: &mut dyn BufRead) -> Result<Self::Input, ReadError> {
+        let entries = read_lines(reader, str::parse)?;
+        Ok(Decoder { entries })
+    }
+
+    fn part1(decoder: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        Ok(decoder.count_known_numbers().into())
+    }
+
+    fn part2(decoder: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        let mut decoder = decoder.clone();
+        decoder.decode();
+        Ok(decoder.sum_values().into())
+    }
+}
+
+/// Lit segments of each digit on a well wired display, in alphabetical order
+pub(crate) const SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+#[derive(Clone)]
+pub struct Decoder {
+    entries: Vec<Entry>,
+}
+
+impl Decoder {
+    pub fn decode(&mut self) {
+        for entry in &mut self.entries {
+            (*entry).decode();
+        }
+    }
+
+    pub fn count_known_numbers(&self) -> usize {
+        self.entries
+            .iter()
+            .flat_map(|e| e.digits.iter().filter_map(|d| d.output))
+            .count()
+    }
+
+    pub fn sum_values(&self) -> u64 {
+        self.entries.iter().map(|e| e.value().unwrap()).sum()
+    }
+}
+
+impl FromStr for Decoder {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let entries = parse_lines(input, str::parse)?;
+        Ok(Decoder { entries })
+    }
+}
+
+/*
+  0:      1:      2:      3:      4:
+ aaaa    ....    aaaa    aaaa    ....
+b    c  .    c  .    c  .    c  b    c
+b    c  .    c  .    c  .    c  b    c
+ ....    ....    dddd    dddd    dddd
+e    f  .    f  e    .  .    f  .    f
+e    f  .    f  e    .  .    f  .    f
+ gggg    ....    gggg    gggg    ....
+
+  5:      6:      7:      8:      9:
+ aaaa    aaaa    aaaa    aaaa    aaaa
+b    .  b    .  .    c  b    c  b    c
+b    .  b    .  .    c  b    c  b    c
+ dddd    dddd    ....    dddd    dddd
+.    f  e    f  .    f  e    f  .    f
+.    f  e    f  .    f  e    f  .    f
+ gggg    gggg    ....    gggg    gggg
+*/
+
+#[derive(Clone)]
+pub struct Entry {
+    signals: Vec<String>,
+    digits: Vec<Digit>,
+    decode_map: HashMap<char, char>,
+}
+
+impl Entry {
+    pub fn decode(&mut self) {
+        self.try_decode()
+            .expect("entries are checked to be decodable when parsed");
+    }
+
+    /// Figures out the wiring and decodes the output digits with it. Fails if no wiring turns the
+    /// signal patterns into the 10 digits.
+    fn try_decode(&mut self) -> Option<()> {
+        let mut sorted_signals: Vec<HashSet<char>> = self
+            .signals
+            .iter()
+            .map(|s| HashSet::from_iter(s.chars()))
+            .collect();
+
+        // Sort signals by their length. That would put in place the signals whose lenghs are known
+        sorted_signals.sort_by_key(|s| s.len());
+
+        if let [s1, s7, s4, rest @ .., _s8] = &sorted_signals[0..=9] {
+            let with_five = &rest[0..=2];
+
+            // The 'a' segment has to be the difference between s1 and s7
+            let a = s7.difference(s1).nth(0)?;
+            self.decode_map.insert(*a, 'a');
+
+            // s3 has 5 segments, and it's the only one whose difference with 7 leaves two segments
+            let s3 = with_five
+                .iter()
+                .filter(|s| s.difference(s7).count() == 2)
+                .nth(0)?;
+
+            // Given s3 and s4
+            // -> `b` is in 4, but not in 3
+            // -> `g` is in 3, but not in 4, and it's not 'a'
+            let b = s4.difference(s3).nth(0)?;
+            self.decode_map.insert(*b, 'b');
+
+            let g = s3.difference(s4).filter(|&chr| *chr != *a).nth(0)?;
+            self.decode_map.insert(*g, 'g');
+
+            // Given s3 and s7
+            // -> `d` is in 3, but not in 7, and it's not `g`
+            let d = s3.difference(s7).filter(|&chr| *chr != *g).nth(0)?;
+            self.decode_map.insert(*d, 'd');
+
+            // s2, s3 and s5 have 5 segments.
+            // -> we know s3
+            // -> s2 has no `b`
+            // -> s5 has to be the other one
+            let s2 = with_five
+                .iter()
+                .filter(|&s| s != s3 && !s.contains(b))
+                .nth(0)?;
+            // Given s2 and s3,
+            // -> `f` is in s3 but not in s2
+            // -> `e` is in s2 but not in s3
+            let f = s3.difference(s2).nth(0)?;
+            self.decode_map.insert(*f, 'f');
+
+            let e = s2.difference(s3).nth(0)?;
+            self.decode_map.insert(*e, 'e');
+
+            // `c` is segment in s1 that is not `f`
+            let c = s1.iter().filter(|&chr| *chr != *f).nth(0)?;
+            self.decode_map.insert(*c, 'c');
+        } else {
+            return None;
+        }
+
+        // Every signal has to be a different digit for the wiring to be right
+        let mut seen = [false; 10];
+        for signal in &self.signals {
+            let output = Digit::from(signal).decode(&self.decode_map)?;
+            if std::mem::replace(&mut seen[output as usize], true) {
+                return None;
+            }
+        }
+
+        for digit in &mut self.digits {
+            (*digit).decode(&self.decode_map);
+        }
+
+        Some(())
+    }
+
+    /// The number shown by the output digits, if all of them are known
+    pub fn value(&self) -> Option<u64> {
+        let mut n: u64 = 0;
+        for (exp, digit) in self.digits.iter().rev().enumerate() {
+            n += digit.output? as u64 * 10_u64.pow(exp as u32);
+        }
+
+        Some(n)
+    }
+}
+
+impl FromStr for Entry {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let (signals, digits_part) = input
+            .split_once('|')
+            .ok_or_else(|| ParseError::new(input.len() + 1, "a `|` separator"))?;
+        let separator_column = signals.len() + 1;
+
+        let signals = signals
+            .split_whitespace()
+            .map(|s| check_segments(input, s).map(String::from))
+            .collect::<Result<Vec<String>, ParseError>>()?;
+
+        if signals.len() != 10 {
+            return Err(ParseError::new(separator_column, "10 signal patterns"));
+        }
+
+        let digits = digits_part
+            .split_whitespace()
+            .map(|d| check_segments(input, d).map(Digit::from))
+            .collect::<Result<Vec<Digit>, ParseError>>()?;
+
+        if digits.len() != 4 {
+            return Err(ParseError::new(input.len() + 1, "4 output digits"));
+        }
+
+        let entry = Entry {
+            signals,
+            digits,
+            decode_map: HashMap::new(),
+        };
+
+        // Decode a copy upfront, so decoding can't fail later on
+        let mut decoded = entry.clone();
+        if decoded.try_decode().is_none() {
+            return Err(ParseError::new(1, "the signal patterns of the 10 digits"));
+        }
+        let mut outputs = digits_part.split_whitespace().zip(&decoded.digits);
+        if let Some((pattern, _)) = outputs.find(|(_, digit)| digit.output.is_none()) {
+            return Err(ParseError::at(input, pattern, "the pattern of a digit"));
+        }
+
+        Ok(entry)
+    }
+}
+
+/// Makes sure `pattern` (a slice of `line`) only uses the segments `a` to `g`
+fn check_segments<'a>(line: &str, pattern: &'a str) -> Result<&'a str, ParseError> {
+    match pattern
+        .char_indices()
+        .find(|(_, c)| !('a'..='g').contains(c))
+    {
+        Some((i, _)) => Err(ParseError::at(
+            line,
+            &pattern[i..],
+            "a segment from `a` to `g`",
+        )),
+        None => Ok(pattern),
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Digit {
+    raw_input: String,
+    output: Option<u8>,
+}
+
+impl Digit {
+    fn guess_output(input: &str) -> Option<u8> {
+        let segment_count = input.len();
+        match segment_count {
+            2 => Some(1),
+            3 => Some(7),
+            4 => Some(4),
+            7 => Some(8),
+            _ => None,
+        }
+    }
+
+    fn parse_segments(segments: &str) -> Option<u8> {
+        SEGMENTS
+            .iter()
+            .position(|&digit| digit == segments)
+            .map(|digit| digit as u8)
+    }
+
+    fn decode(&mut self, decode_map: &HashMap<char, char>) -> Option<u8> {
+        let segments: Option<Vec<char>> = self
+            .raw_input
+            .chars()
+            .map(|c| decode_map.get(&c).copied())
+            .collect();
+
+        self.output = segments.and_then(|mut segments| {
+            segments.sort_unstable();
+            Self::parse_segments(&String::from_iter(segments))
+        });
+        self.output
+    }
+}
+
+impl<T: AsRef<str>> From<T> for Digit {
+    fn from(raw_input: T) -> Self {
+        let raw_input = raw_input.as_ref();
+        let output = Self::guess_output(raw_input);
+
+        Digit {
+            raw_input: raw_input.into(),
+            output,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{generate, Rng};
+
+    #[test]
+    fn it_returns_entry_value_after_decoding() {
+        let mut entry: Entry =
+            "fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg"
+                .parse()
+                .unwrap();
+        assert_eq!(entry.value(), None);
+
+        entry.decode();
+        assert_eq!(entry.value(), Some(1197));
+    }
+
+    #[test]
+    fn it_fails_to_parse_bad_entries() {
+        let err = "fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef cg cg fdcagb cbg"
+            .parse::<Entry>()
+            .err();
+        assert_eq!(err, Some(ParseError::new(76, "a `|` separator")));
+
+        let err = "fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb | cg cg fdcagb cbg"
+            .parse::<Entry>()
+            .err();
+        assert_eq!(err, Some(ParseError::new(52, "10 signal patterns")));
+
+        let err = "fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cx fdcagb cbg"
+            .parse::<Entry>()
+            .err();
+        assert_eq!(err, Some(ParseError::new(66, "a segment from `a` to `g`")));
+
+        // With a 4 shown twice and no 8, the patterns can't be the 10 digits
+        let err = "fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb gfac | cg cg fdcagb cbg"
+            .parse::<Entry>()
+            .err();
+        assert_eq!(
+            err,
+            Some(ParseError::new(1, "the signal patterns of the 10 digits"))
+        );
+
+        let err = "fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcag cbg"
+            .parse::<Entry>()
+            .err();
+        assert_eq!(err, Some(ParseError::new(68, "the pattern of a digit")));
+    }
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day08/example1.txt");
+
+    #[test]
+    fn it_parses_entries() {
+        let decoder = TEST_INPUT.parse::<Decoder>().unwrap();
+
+        assert_eq!(decoder.entries.len(), 10);
+
+        let first_entry = &decoder.entries[0];
+        assert_eq!(first_entry.signals.len(), 10);
+        assert_eq!(first_entry.digits.len(), 4);
+    }
+
+    #[test]
+    fn it_returns_known_numbers() {
+        let decoder = TEST_INPUT.parse::<Decoder>().unwrap();
+        assert_eq!(decoder.count_known_numbers(), 26);
+    }
+
+    /// Every order of `items`
+    fn permutations(items: &[char]) -> Vec<Vec<char>> {
+        if items.is_empty() {
+            return vec![vec![]];
+        }
+
+        (0..items.len())
+            .flat_map(|i| {
+                let mut rest = items.to_vec();
+                let first = rest.remove(i);
+                permutations(&rest).into_iter().map(move |mut order| {
+                    order.insert(0, first);
+                    order
+                })
+            })
+            .collect()
+    }
+
+    /// Output value of an entry, trying every wiring until one turns all the patterns into digits
+    fn brute_force_value(line: &str) -> u64 {
+        let (patterns, output) = line.split_once(" | ").unwrap();
+        let digit = |wiring: &[char], pattern: &str| {
+            let mut segments: Vec<char> = pattern
+                .chars()
+                .map(|wire| (b'a' + wiring.iter().position(|&w| w == wire).unwrap() as u8) as char)
+                .collect();
+            segments.sort_unstable();
+            let segments: String = segments.into_iter().collect();
+            SEGMENTS.iter().position(|&digit| digit == segments)
+        };
+
+        let wiring = permutations(&['a', 'b', 'c', 'd', 'e', 'f', 'g'])
+            .into_iter()
+            .find(|wiring| {
+                patterns
+                    .split_whitespace()
+                    .all(|pattern| digit(wiring, pattern).is_some())
+            })
+            .unwrap();
+
+        output.split_whitespace().fold(0, |value, pattern| {
+            value * 10 + digit(&wiring, pattern).unwrap() as u64
+        })
+    }
+
+    #[test]
+    fn it_decodes_like_trying_every_wiring() {
+        let mut rng = Rng::new(8);
+        let mut lines = vec![
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cagedb cagedb cagedb cagedb"
+                .to_owned(),
+        ];
+
+        // Generated entries, with outputs drawn from their own patterns to repeat digits often
+        for seed in 0..40 {
+            for line in generate(2021, 8, 5, seed).unwrap().lines() {
+                let patterns: Vec<&str> = line.split(" | ").next().unwrap().split(' ').collect();
+                let same = rng.one_in(4);
+                let first = patterns[rng.below(10) as usize];
+                let output: Vec<&str> = (0..4)
+                    .map(|_| {
+                        if same {
+                            first
+                        } else {
+                            patterns[rng.below(10) as usize]
+                        }
+                    })
+                    .collect();
+                lines.push(format!("{} | {}", patterns.join(" "), output.join(" ")));
+            }
+        }
+
+        for line in lines {
+            let mut entry: Entry = line.parse().unwrap();
+            entry.decode();
+            assert_eq!(entry.value(), Some(brute_force_value(&line)), "{}", line);
+        }
+    }
+
+    #[test]
+    fn it_sums_outputs() {
+        let mut decoder = TEST_INPUT.parse::<Decoder>().unwrap();
+        decoder.decode();
+
+        assert_eq!(decoder.sum_values(), 61229);
+    }
+}
@@ -0,0 +1,283 @@
+use std::str::FromStr;
+
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::repl::{point_arg, Command, Explore};
+use crate::visual::{Colour, Recorder, Visualize};
+use crate::{Answer, Params, ParseError, Solution};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = HeightMap;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part1(map: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        Ok(map.risk_level_sum().into())
+    }
+
+    fn part2(map: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        map.biggest_basins_product()?.try_into()
+    }
+}
+
+#[derive(Clone)]
+pub struct HeightMap {
+    grid: Grid<u8>,
+}
+
+impl HeightMap {
+    pub fn low_points(&self) -> Vec<Point> {
+        self.grid
+            .cells()
+            .filter(|&(point, measurement)| {
+                self.grid
+                    .neighbours4(point)
+                    .all(|neighbour| self.grid[neighbour] > *measurement)
+            })
+            .map(|(point, _)| point)
+            .collect()
+    }
+
+    pub fn biggest_basins_product(&self) -> Result<u128, String> {
+        let mut exploration_map = Grid::new(self.grid.width(), self.grid.height(), false);
+
+        let mut basin_sizes: Vec<usize> = self
+            .low_points()
+            .into_iter()
+            .map(|p| self.basin_size(p, &mut exploration_map))
+            .collect();
+
+        basin_sizes.sort_unstable_by(|a, b| b.cmp(a));
+        (basin_sizes.into_iter().take(3))
+            .try_fold(1_u128, |product, size| product.checked_mul(size as u128))
+            .ok_or_else(|| "the product of the basin sizes does not fit in 128 bits".to_owned())
+    }
+
+    /// Points in the basin of `point` not explored yet, marking them as explored. A work list
+    /// rather than recursion keeps big basins from overflowing the stack.
+    fn basin_size(&self, point: Point, exploration_map: &mut Grid<bool>) -> usize {
+        let mut size = 0;
+        let mut to_explore = vec![point];
+
+        while let Some(point) = to_explore.pop() {
+            if exploration_map[point] {
+                continue;
+            }
+            exploration_map[point] = true;
+
+            if self.grid[point] != 9 {
+                size += 1;
+                to_explore.extend(self.grid.neighbours4(point));
+            }
+        }
+
+        size
+    }
+
+    pub fn risk_level_sum(&self) -> u64 {
+        self.low_points()
+            .into_iter()
+            .map(|point| u64::from(self.grid[point]) + 1)
+            .sum()
+    }
+}
+
+/// Heights go from green valleys to brown peaks, basins fill with blue from their red low points
+impl Visualize for Day09 {
+    const COLOURS: &'static [Colour] = &[
+        [34, 92, 48],
+        [52, 108, 52],
+        [74, 122, 56],
+        [98, 134, 60],
+        [122, 142, 66],
+        [144, 146, 74],
+        [158, 142, 84],
+        [164, 132, 92],
+        [156, 118, 96],
+        [110, 84, 72],
+        [52, 120, 210],
+        [220, 40, 40],
+    ];
+
+    /// A frame per wave of every basin filling up at once from its low point
+    fn record(map: &Self::Input, _params: &Params, recorder: &mut Recorder) -> Result<(), String> {
+        let mut frame = map.grid.clone();
+        recorder.record(frame.clone())?;
+
+        let mut wave = map.low_points();
+        for &point in &wave {
+            frame[point] = 11;
+        }
+
+        while !wave.is_empty() {
+            recorder.record(frame.clone())?;
+
+            let mut next = vec![];
+            for point in wave {
+                for neighbour in map.grid.neighbours4(point) {
+                    if frame[neighbour] < 9 {
+                        frame[neighbour] = 10;
+                        next.push(neighbour);
+                    }
+                }
+            }
+            wave = next;
+        }
+        Ok(())
+    }
+}
+
+impl Explore for Day09 {
+    type State = HeightMap;
+
+    const COMMANDS: &'static [Command] = &[
+        ("basin", "<x,y>", "Size of the basin a point is in"),
+        ("low", "", "List the low points and their risk levels"),
+        ("show", "", "Show the height map"),
+    ];
+
+    fn start(map: &HeightMap) -> HeightMap {
+        map.clone()
+    }
+
+    fn run(map: &mut HeightMap, name: &str, args: &[&str]) -> Result<String, String> {
+        match name {
+            "basin" => {
+                let point = point_arg(args, 0)?;
+                if !map.grid.contains(point) {
+                    return Err(format!(
+                        "{},{} is off the {}x{} map",
+                        point.x,
+                        point.y,
+                        map.grid.width(),
+                        map.grid.height()
+                    ));
+                }
+
+                let mut exploration_map = Grid::new(map.grid.width(), map.grid.height(), false);
+                Ok(match map.basin_size(point, &mut exploration_map) {
+                    0 => format!("{},{} is a peak, in no basin", point.x, point.y),
+                    size => format!("The basin of {},{} has {} points", point.x, point.y, size),
+                })
+            }
+            "low" => Ok((map.low_points().into_iter())
+                .map(|point| format!("{},{}: risk {}", point.x, point.y, map.grid[point] + 1))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            "show" => Ok(map.grid.to_string().trim_end().to_owned()),
+            _ => Err(format!("command `{}` is not handled", name)),
+        }
+    }
+}
+
+impl FromStr for HeightMap {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse_digits(input)?;
+        Ok(HeightMap { grid })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day09/example1.txt");
+
+    #[test]
+    fn it_parses_input() {
+        let map = TEST_INPUT.parse::<HeightMap>().unwrap();
+        assert_eq!(map.grid.height(), 5);
+    }
+
+    #[test]
+    fn it_finds_low_points() {
+        let map = TEST_INPUT.parse::<HeightMap>().unwrap();
+        assert_eq!(
+            map.low_points(),
+            vec![
+                Point::new(1, 0),
+                Point::new(9, 0),
+                Point::new(2, 2),
+                Point::new(6, 4)
+            ]
+        )
+    }
+
+    #[test]
+    fn it_sums_risk_levels() {
+        let map = TEST_INPUT.parse::<HeightMap>().unwrap();
+        assert_eq!(map.risk_level_sum(), 15);
+    }
+
+    #[test]
+    fn it_records_basins_filling_up() {
+        let map = TEST_INPUT.parse::<HeightMap>().unwrap();
+        let mut recorder = Recorder::new();
+        Day09::record(&map, &Params::default(), &mut recorder).unwrap();
+
+        let last = recorder.frames().last().unwrap();
+        let filled = last.cells().filter(|(_, &level)| level >= 10).count();
+        assert_eq!(filled, 3 + 9 + 14 + 9);
+        assert_eq!(last.cells().filter(|(_, &level)| level == 11).count(), 4);
+    }
+
+    #[test]
+    fn it_stops_recording_long_basins() {
+        // A long thin basin fills up one point per wave, with a frame of the whole map each
+        let map = format!("0{}", "1".repeat(199))
+            .parse::<HeightMap>()
+            .unwrap();
+
+        let mut recorder = Recorder::with_limit(50 * 200);
+        let err = Day09::record(&map, &Params::default(), &mut recorder).unwrap_err();
+        assert!(err.contains("too many cells"), "{}", err);
+        assert_eq!(recorder.frames().len(), 50);
+    }
+
+    #[test]
+    fn it_finds_basin_sizes() {
+        let map = TEST_INPUT.parse::<HeightMap>().unwrap();
+
+        assert_eq!(map.biggest_basins_product(), Ok(1134));
+    }
+
+    #[test]
+    fn it_fills_big_basins() {
+        // A basin this big used to overflow the stack
+        let mut rows = vec!["1".repeat(1000); 1000];
+        rows[0].replace_range(0..1, "0");
+        let map = rows.join("\n").parse::<HeightMap>().unwrap();
+        assert_eq!(map.biggest_basins_product(), Ok(1_000_000));
+
+        // Three basins of 1700 points, whose product used to overflow 32 bits
+        let block = |first| format!("{}{}", first, "1".repeat(16));
+        let mut rows = vec![vec![block('1'); 3].join("9"); 100];
+        rows[0] = vec![block('0'); 3].join("9");
+        let map = rows.join("\n").parse::<HeightMap>().unwrap();
+        assert_eq!(
+            Day09::part2(&map, &Params::default()),
+            Ok(Answer::Number(4_913_000_000))
+        );
+    }
+
+    #[test]
+    fn it_measures_basins_in_the_repl() {
+        let mut map = Day09::start(&TEST_INPUT.parse().unwrap());
+
+        assert_eq!(
+            Day09::run(&mut map, "basin", &["9,0"]),
+            Ok("The basin of 9,0 has 9 points".to_owned())
+        );
+        assert_eq!(
+            Day09::run(&mut map, "basin", &["2,0"]),
+            Ok("2,0 is a peak, in no basin".to_owned())
+        );
+        assert!(Day09::run(&mut map, "basin", &["10,0"]).is_err());
+    }
+}
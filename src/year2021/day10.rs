@@ -0,0 +1,197 @@
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::input::{read_lines, ReadError};
+use crate::parse::parse_lines;
+use crate::{Answer, Params, ParseError, Solution};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref PAIR_MAP: HashMap<char, char> =
+        HashMap::from([('{', '}'), ('(', ')'), ('[', ']'), ('<', '>')]);
+    static ref CORRUPT_SCORE_MAP: HashMap<char, u64> =
+        HashMap::from([(')', 3), (']', 57), ('}', 1197), ('>', 25137)]);
+    static ref AUTOCOMPLETE_SCORE_MAP: HashMap<char, u64> =
+        HashMap::from([('(', 1), ('[', 2), ('{', 3), ('<', 4)]);
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = NavParser;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn read(reader: &mut dyn BufRead) -> Result<Self::Input, ReadError> {
+        let lines = read_lines(reader, str::parse)?;
+        Ok(NavParser { lines })
+    }
+
+    fn part1(nav: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        Ok(nav.corruption_score().into())
+    }
+
+    fn part2(nav: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        nav.completion_score().map(Answer::from)
+    }
+}
+
+pub struct NavParser {
+    lines: Vec<Line>,
+}
+
+impl NavParser {
+    pub fn corruption_score(&self) -> u64 {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                Line::Corrupted(chr) => Some(CORRUPT_SCORE_MAP[chr]),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Middle score of the incomplete lines, or 0 if there are none
+    pub fn completion_score(&self) -> Result<u64, String> {
+        let mut scores: Vec<u64> = self
+            .lines
+            .iter()
+            .filter_map(|line| match line {
+                Line::Incomplete(still_open) => Some(
+                    (still_open.iter().rev())
+                        .try_fold(0_u64, |score, chr| {
+                            score
+                                .checked_mul(5)?
+                                .checked_add(AUTOCOMPLETE_SCORE_MAP[chr])
+                        })
+                        .ok_or_else(|| {
+                            format!(
+                                "the score of a line with {} chunks left open does not fit in \
+                                 64 bits",
+                                still_open.len()
+                            )
+                        }),
+                ),
+                _ => None,
+            })
+            .collect::<Result<_, _>>()?;
+
+        scores.sort_unstable();
+        Ok(scores.get(scores.len() / 2).copied().unwrap_or(0))
+    }
+}
+
+impl FromStr for NavParser {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let lines = parse_lines(input, str::parse)?;
+        Ok(NavParser { lines })
+    }
+}
+
+/// A line of chunks, along with the chunks still open if it's incomplete, or the first
+/// unexpected closing character if it's corrupted
+#[derive(Debug, PartialEq)]
+enum Line {
+    Complete,
+    Incomplete(Vec<char>),
+    Corrupted(char),
+}
+
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(raw_input: &str) -> Result<Self, ParseError> {
+        let is_bracket =
+            |chr: &char| PAIR_MAP.contains_key(chr) || CORRUPT_SCORE_MAP.contains_key(chr);
+        if let Some((col, _)) = raw_input
+            .chars()
+            .enumerate()
+            .find(|(_, chr)| !is_bracket(chr))
+        {
+            return Err(ParseError::new(col + 1, "one of `()[]{}<>`"));
+        }
+
+        let mut stack: Vec<char> = vec![];
+
+        let opening_chars: HashSet<&char> = PAIR_MAP.keys().collect();
+
+        for chr in raw_input.chars() {
+            if opening_chars.contains(&chr) {
+                stack.push(chr);
+            } else if let Some(current_open) = stack.last() {
+                if PAIR_MAP[current_open] == chr {
+                    stack.pop();
+                } else {
+                    return Ok(Line::Corrupted(chr));
+                }
+            }
+        }
+
+        if stack.is_empty() {
+            Ok(Line::Complete)
+        } else {
+            Ok(Line::Incomplete(stack))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day10/example1.txt");
+
+    #[test]
+    fn it_parses_lines() {
+        let nav = TEST_INPUT.parse::<NavParser>().unwrap();
+        assert_eq!(nav.lines.len(), 10);
+    }
+
+    #[test]
+    fn it_fails_to_parse_lines_with_other_chars() {
+        let err = "[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(x".parse::<NavParser>();
+        assert_eq!(
+            err.err(),
+            Some(ParseError::new(23, "one of `()[]{}<>`").offset_lines(1))
+        );
+    }
+
+    #[test]
+    fn it_calculates_corrupted_score() {
+        let nav = TEST_INPUT.parse::<NavParser>().unwrap();
+        assert_eq!(nav.corruption_score(), 26397);
+    }
+
+    #[test]
+    fn it_calculates_autocomplete_score() {
+        let nav = TEST_INPUT.parse::<NavParser>().unwrap();
+        assert_eq!(nav.completion_score(), Ok(288957));
+    }
+
+    #[test]
+    fn it_scores_inputs_without_incomplete_lines() {
+        let nav = "\n{()}\n".parse::<NavParser>().unwrap();
+        assert_eq!(nav.lines, vec![Line::Complete, Line::Complete]);
+        assert_eq!(nav.completion_score(), Ok(0));
+        assert_eq!(nav.corruption_score(), 0);
+    }
+
+    #[test]
+    fn it_fails_to_score_lines_left_too_open() {
+        let open = "<".repeat(27);
+        let nav = open.parse::<NavParser>().unwrap();
+        assert_eq!(nav.completion_score(), Ok(5_u64.pow(27) - 1));
+
+        // Every chunk left open multiplies the score by 5, and a 28th takes it past 64 bits
+        let nav = format!("{}\n{}<", open, open).parse::<NavParser>().unwrap();
+        assert_eq!(
+            nav.completion_score(),
+            Err("the score of a line with 28 chunks left open does not fit in 64 bits".to_owned())
+        );
+    }
+}
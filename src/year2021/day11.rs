@@ -0,0 +1,434 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::repl::{arg, Command, Explore};
+use crate::visual::{Colour, Recorder, Visualize};
+use crate::{Answer, Params, ParseError, Solution};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = OctoLights;
+
+    const PARAMS: &'static [&'static str] = &["steps"];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part1(octos: &Self::Input, params: &Params) -> Result<Answer, String> {
+        let steps = params.get("steps").unwrap_or(100);
+        let steps = usize::try_from(steps).unwrap_or(usize::MAX);
+        octos.check_steps(steps)?;
+        Ok(octos.clone().step(steps).into())
+    }
+
+    fn part2(octos: &Self::Input, _params: &Params) -> Result<Answer, String> {
+        octos
+            .sync()
+            .map(Answer::from)
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct OctoLights {
+    grid: Grid<u8>,
+    /// Which octopuses flashed in the current step, and the ones still to light up their
+    /// neighbours. Kept between steps so that stepping doesn't allocate.
+    flashed: Grid<bool>,
+    must_radiate: VecDeque<Point>,
+}
+
+/// Most octopus steps, the steps taken times the octopuses in the grid, that part 1 or `sync` go
+/// through, so a huge grid or a huge `--steps` is an error in seconds
+const MAX_OCTOPUS_STEPS: usize = 1 << 27;
+
+/// Why `sync` found no step where every octopus flashes at once
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncError {
+    /// The grid came back to an earlier state, so no step ever will
+    Cycle,
+    /// None of the first that many steps did, but a later one still might
+    GaveUp(usize),
+}
+
+impl fmt::Display for SyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncError::Cycle => write!(f, "the octopuses never all flash at once"),
+            SyncError::GaveUp(steps) => {
+                write!(f, "no step in the first {} flashes every octopus", steps)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SyncError {}
+
+impl OctoLights {
+    pub fn step(&mut self, steps: usize) -> usize {
+        (0..steps).map(|_| self.step_once(&mut |_, _| {})).sum()
+    }
+
+    /// The first step where every octopus flashes at once. The search stops when the grid comes
+    /// back to a state it was in before, which it then keeps going around, or after
+    /// `max_steps`.
+    pub fn sync(&self) -> Result<usize, SyncError> {
+        self.sync_within(self.max_steps())
+    }
+
+    /// Most steps of this grid that fit in `MAX_OCTOPUS_STEPS`, and at least 1
+    fn max_steps(&self) -> usize {
+        (MAX_OCTOPUS_STEPS / self.grid.len().max(1)).max(1)
+    }
+
+    /// Checks that `steps` steps of this grid fit in `MAX_OCTOPUS_STEPS`
+    fn check_steps(&self, steps: usize) -> Result<(), String> {
+        if steps > self.max_steps() {
+            return Err(format!(
+                "{} steps of {} octopuses are too many, the most is {} octopus steps",
+                steps,
+                self.grid.len(),
+                MAX_OCTOPUS_STEPS
+            ));
+        }
+        Ok(())
+    }
+
+    fn sync_within(&self, max_steps: usize) -> Result<usize, SyncError> {
+        let (mut steps, mut flashes) = (0, 0);
+        self.clone()
+            .step_until_sync(max_steps, &mut steps, &mut flashes)?;
+        Ok(steps)
+    }
+
+    /// Steps until every octopus flashes at once, for at most `max_steps`, adding the steps taken
+    /// and the flashes seen to `steps` and `flashes`. Stops early with `SyncError::Cycle` when the
+    /// grid comes back to a state it was in since the search started.
+    fn step_until_sync(
+        &mut self,
+        max_steps: usize,
+        steps: &mut usize,
+        flashes: &mut usize,
+    ) -> Result<(), SyncError> {
+        let mut seen = self.grid.clone();
+
+        for step in 1..=max_steps {
+            let flashed = self.step(1);
+            *steps += 1;
+            *flashes += flashed;
+            if flashed == self.grid.len() {
+                return Ok(());
+            }
+
+            // Brent's cycle detection: compare with the state kept at the last power of two
+            if self.grid == seen {
+                return Err(SyncError::Cycle);
+            }
+            if step.is_power_of_two() {
+                seen = self.grid.clone();
+            }
+        }
+
+        Err(SyncError::GaveUp(max_steps))
+    }
+
+    /// Runs a single step and returns how many octopuses flashed. `wave` sees the grid and the
+    /// octopuses that flashed so far after the energy goes up, and after every wave of flashes.
+    fn step_once(&mut self, wave: &mut dyn FnMut(&Grid<u8>, &Grid<bool>)) -> usize {
+        let mut flashes = 0;
+
+        for ((position, val), (_, flashed)) in self.grid.cells_mut().zip(self.flashed.cells_mut()) {
+            *val += 1;
+            *flashed = *val > 9;
+            if *flashed {
+                self.must_radiate.push_back(position);
+                flashes += 1;
+            }
+        }
+        wave(&self.grid, &self.flashed);
+
+        while !self.must_radiate.is_empty() {
+            for _ in 0..self.must_radiate.len() {
+                let point = self.must_radiate.pop_front().unwrap();
+                self.grid[point] = 0;
+
+                for neighbour in self.grid.neighbours8(point) {
+                    if self.flashed[neighbour] {
+                        continue;
+                    }
+
+                    let next_val = self.grid[neighbour] + 1;
+                    if next_val > 9 {
+                        self.must_radiate.push_back(neighbour);
+                        self.flashed[neighbour] = true;
+                        flashes += 1;
+                    }
+                    self.grid[neighbour] = next_val;
+                }
+            }
+            wave(&self.grid, &self.flashed);
+        }
+
+        flashes
+    }
+}
+
+/// Energy levels from 0 to 9 go from dark to bright, and flashing octopuses are white
+impl Visualize for Day11 {
+    const COLOURS: &'static [Colour] = &[
+        [16, 16, 40],
+        [24, 28, 64],
+        [32, 44, 92],
+        [40, 62, 118],
+        [52, 84, 140],
+        [70, 110, 156],
+        [96, 138, 166],
+        [130, 166, 170],
+        [170, 192, 168],
+        [212, 216, 160],
+        [255, 255, 255],
+    ];
+
+    /// A frame per wave of flashes, for as many steps as part 1 takes
+    fn record(octos: &Self::Input, params: &Params, recorder: &mut Recorder) -> Result<(), String> {
+        let steps = params.get("steps").unwrap_or(100);
+        let mut octos = octos.clone();
+        recorder.record(octos.grid.clone())?;
+
+        for _ in 0..steps {
+            let mut recorded = Ok(());
+            octos.step_once(&mut |grid, flashed| {
+                if recorded.is_err() {
+                    return;
+                }
+                let mut frame = grid.clone();
+                for (point, _) in flashed.cells().filter(|(_, &flashed)| flashed) {
+                    frame[point] = 10;
+                }
+                recorded = recorder.record(frame);
+            });
+            recorded?;
+        }
+        Ok(())
+    }
+}
+
+/// Octopuses stepped through in `aoc repl`, with the steps taken and the flashes seen so far
+pub struct Cavern {
+    octos: OctoLights,
+    steps: usize,
+    flashes: usize,
+}
+
+impl Explore for Day11 {
+    type State = Cavern;
+
+    const COMMANDS: &'static [Command] = &[
+        (
+            "step",
+            "[n]",
+            "Take n steps, 1 by default, and count the flashes",
+        ),
+        ("sync", "", "Step until every octopus flashes at once"),
+        ("show", "", "Show the energy levels"),
+    ];
+
+    fn start(octos: &OctoLights) -> Cavern {
+        Cavern {
+            octos: octos.clone(),
+            steps: 0,
+            flashes: 0,
+        }
+    }
+
+    fn run(cavern: &mut Cavern, name: &str, args: &[&str]) -> Result<String, String> {
+        match name {
+            "step" => {
+                let steps = arg(args, 0, Some(1), "how many steps to take")?;
+                cavern.octos.check_steps(steps)?;
+                let flashes = cavern.octos.step(steps);
+                cavern.steps += steps;
+                cavern.flashes += flashes;
+                Ok(format!(
+                    "{} flashes, {} in {} steps so far",
+                    flashes, cavern.flashes, cavern.steps
+                ))
+            }
+            "sync" => {
+                let max_steps = cavern.octos.max_steps();
+                let synced =
+                    cavern
+                        .octos
+                        .step_until_sync(max_steps, &mut cavern.steps, &mut cavern.flashes);
+                match synced {
+                    Ok(()) => Ok(format!("Every octopus flashes at step {}", cavern.steps)),
+                    Err(SyncError::GaveUp(steps)) => Err(format!(
+                        "no step in the next {} flashes every octopus, at step {} now",
+                        steps, cavern.steps
+                    )),
+                    Err(err) => Err(format!("{}, at step {} now", err, cavern.steps)),
+                }
+            }
+            "show" => Ok(format!(
+                "After {} steps:\n{}",
+                cavern.steps,
+                cavern.octos.to_string().trim_end()
+            )),
+            _ => Err(format!("command `{}` is not handled", name)),
+        }
+    }
+}
+
+impl FromStr for OctoLights {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse_digits(input)?;
+        Ok(OctoLights {
+            flashed: Grid::new(grid.width(), grid.height(), false),
+            must_radiate: VecDeque::new(),
+            grid,
+        })
+    }
+}
+
+impl fmt::Display for OctoLights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::generate;
+
+    #[test]
+    fn it_propagates_flashes() {
+        let mut octos = "11111\n19991\n19191\n19991\n11111"
+            .parse::<OctoLights>()
+            .unwrap();
+        octos.step(1);
+
+        assert_eq!(format!("{}", octos), "34543\n40004\n50005\n40004\n34543\n");
+    }
+
+    #[test]
+    fn it_records_waves_of_flashes() {
+        let octos = "11111\n19991\n19191\n19991\n11111"
+            .parse::<OctoLights>()
+            .unwrap();
+        let mut params = Params::default();
+        params.set("steps", 1);
+        let mut recorder = Recorder::new();
+        Day11::record(&octos, &params, &mut recorder).unwrap();
+
+        let flashing: Vec<usize> = recorder
+            .frames()
+            .iter()
+            .map(|frame| frame.cells().filter(|(_, &level)| level == 10).count())
+            .collect();
+        assert_eq!(flashing, vec![0, 8, 9, 9]);
+    }
+
+    #[test]
+    fn it_stops_recording_many_steps() {
+        let octos = TEST_INPUT.parse::<OctoLights>().unwrap();
+        let mut params = Params::default();
+        params.set("steps", u64::MAX);
+        let mut recorder = Recorder::with_limit(1000 * 100);
+
+        assert!(Day11::record(&octos, &params, &mut recorder).is_err());
+        assert_eq!(recorder.frames().len(), 1000);
+    }
+
+    #[test]
+    fn it_gives_up_on_octopuses_that_never_sync() {
+        // This grid goes around a cycle of states where the octopuses never all flash at once
+        let octos = "17\n60".parse::<OctoLights>().unwrap();
+
+        assert_eq!(octos.sync(), Err(SyncError::Cycle));
+        assert_eq!(
+            Day11::part2(&octos, &Params::default()),
+            Err("the octopuses never all flash at once".to_owned())
+        );
+
+        let mut cavern = Day11::start(&octos);
+        let synced = Day11::run(&mut cavern, "sync", &[]).unwrap_err();
+        assert!(synced.starts_with("the octopuses never all flash at once, at step "));
+        assert!(cavern.steps < 100);
+    }
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day11/example1.txt");
+
+    #[test]
+    fn it_counts_flashes() {
+        let mut octos = TEST_INPUT.parse::<OctoLights>().unwrap();
+
+        let mut flash_count = octos.step(10);
+        assert_eq!(flash_count, 204);
+
+        flash_count += octos.step(90); // For a total of 100
+        assert_eq!(flash_count, 1656);
+    }
+
+    #[test]
+    fn it_tells_giving_up_from_never_syncing() {
+        let octos = TEST_INPUT.parse::<OctoLights>().unwrap();
+
+        assert_eq!(octos.sync(), Ok(195));
+        assert_eq!(octos.sync_within(194), Err(SyncError::GaveUp(194)));
+        assert_eq!(
+            SyncError::GaveUp(194).to_string(),
+            "no step in the first 194 flashes every octopus"
+        );
+    }
+
+    #[test]
+    fn it_refuses_too_many_steps() {
+        let octos = TEST_INPUT.parse::<OctoLights>().unwrap();
+        let mut params = Params::default();
+        params.set("steps", u64::MAX);
+        assert!(Day11::part1(&octos, &params).is_err());
+
+        assert!(octos.check_steps(MAX_OCTOPUS_STEPS / 100).is_ok());
+        assert!(octos.check_steps(MAX_OCTOPUS_STEPS / 100 + 1).is_err());
+    }
+
+    #[test]
+    fn it_gives_up_on_big_grids_early() {
+        let octos = generate(2021, 11, 1000, 42)
+            .unwrap()
+            .parse::<OctoLights>()
+            .unwrap();
+        assert_eq!(octos.max_steps(), MAX_OCTOPUS_STEPS / 1_000_000);
+
+        // A debug build steps a grid this big slowly, so give it less work than `sync` does
+        let octos = generate(2021, 11, 300, 42)
+            .unwrap()
+            .parse::<OctoLights>()
+            .unwrap();
+        assert_eq!(octos.sync_within(20), Err(SyncError::GaveUp(20)));
+    }
+
+    #[test]
+    fn it_steps_in_the_repl() {
+        let mut cavern = Day11::start(&TEST_INPUT.parse().unwrap());
+
+        assert_eq!(
+            Day11::run(&mut cavern, "step", &["10"]),
+            Ok("204 flashes, 204 in 10 steps so far".to_owned())
+        );
+        assert_eq!(
+            Day11::run(&mut cavern, "sync", &[]),
+            Ok("Every octopus flashes at step 195".to_owned())
+        );
+        assert!(Day11::run(&mut cavern, "step", &["18446744073709551615"]).is_err());
+        assert!(Day11::run(&mut cavern, "jump", &[]).is_err());
+    }
+}
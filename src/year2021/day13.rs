@@ -0,0 +1,459 @@
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::input::{read_sections, sections, ReadError, Section};
+use crate::parse::{parse_lines, parse_number};
+use crate::repl::{arg, Command, Explore};
+use crate::visual::{check_size, Colour, Recorder, Visualize};
+use crate::{Answer, Params, ParseError, Solution};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = DotGrid;
+
+    /// How many folds to make. Part 2 makes all of them unless told otherwise.
+    const PARAMS: &'static [&'static str] = &["folds"];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn read(reader: &mut dyn BufRead) -> Result<Self::Input, ReadError> {
+        DotGrid::from_sections(
+            read_sections(reader).map(|section| section.map_err(ReadError::from)),
+        )
+    }
+
+    fn part1(dot_grid: &Self::Input, params: &Params) -> Result<Answer, String> {
+        let mut dot_grid = dot_grid.clone();
+        dot_grid.fold(params.get("folds").unwrap_or(1) as usize);
+        Ok(dot_grid.count_visible().into())
+    }
+
+    fn part2(dot_grid: &Self::Input, params: &Params) -> Result<Answer, String> {
+        let mut dot_grid = dot_grid.clone();
+        match params.get("folds") {
+            Some(folds) => dot_grid.fold(folds as usize),
+            None => dot_grid.fold_all(),
+        }
+        dot_grid.drawing().map(Answer::from)
+    }
+}
+
+/// The dots on a paper of `width` × `height`, kept apart so a big paper with few dots stays small
+#[derive(Clone)]
+pub struct DotGrid {
+    dots: HashSet<Point>,
+    width: usize,
+    height: usize,
+    instructions: VecDeque<Fold>,
+}
+
+impl fmt::Display for DotGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let dot = self.dots.contains(&Point::new(x as i64, y as i64));
+                write!(f, "{}", if dot { '#' } else { ' ' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl DotGrid {
+    pub fn count_visible(&self) -> usize {
+        self.dots.len()
+    }
+
+    /// The paper as text, a `#` for every dot, or an error if it's too big to draw
+    pub fn drawing(&self) -> Result<String, String> {
+        check_size(self.width, self.height)?;
+        Ok(self.to_string())
+    }
+
+    pub fn fold(&mut self, n: usize) {
+        for _ in 0..n {
+            match self.instructions.pop_front() {
+                // Folding past the edge leaves the paper as it is
+                Some(Fold::X(fold_point)) => self.width = fold_point.min(self.width),
+                Some(Fold::Y(fold_point)) => self.height = fold_point.min(self.height),
+                None => break,
+            };
+
+            // Dots past the fold land as far from it on the other side
+            let (width, height) = (self.width as i64, self.height as i64);
+            self.dots = (self.dots.iter())
+                .map(|dot| Point::new(mirror(dot.x, width), mirror(dot.y, height)))
+                .filter(|dot| (0..width).contains(&dot.x) && (0..height).contains(&dot.y))
+                .collect();
+        }
+    }
+
+    pub fn fold_all(&mut self) {
+        self.fold(self.instructions.len())
+    }
+
+    /// The paper as a grid of dots
+    fn grid(&self) -> Grid<bool> {
+        let mut grid = Grid::new(self.width, self.height, false);
+        for &dot in &self.dots {
+            grid[dot] = true;
+        }
+        grid
+    }
+}
+
+/// Dots are dark on white paper, and the next fold is a red line
+impl Visualize for Day13 {
+    const COLOURS: &'static [Colour] =
+        &[[24, 24, 32], [236, 232, 220], [30, 30, 60], [210, 50, 50]];
+
+    /// A frame with the line of every fold, and another once folded, for as many folds as part 2
+    /// makes
+    fn record(
+        dot_grid: &Self::Input,
+        params: &Params,
+        recorder: &mut Recorder,
+    ) -> Result<(), String> {
+        // Folds only make the paper smaller, so the first frame is the biggest
+        check_size(dot_grid.width, dot_grid.height)?;
+
+        let mut dot_grid = dot_grid.clone();
+        let folds = params
+            .get("folds")
+            .map_or(dot_grid.instructions.len(), |folds| folds as usize);
+        let paper = |grid: &Grid<bool>| grid.map(|&dot| if dot { 2 } else { 1 });
+
+        recorder.record(paper(&dot_grid.grid()))?;
+        for _ in 0..folds {
+            let mut frame = paper(&dot_grid.grid());
+            match dot_grid.instructions.front() {
+                Some(&Fold::X(x)) if x < frame.width() => {
+                    for y in 0..frame.height() {
+                        frame[Point::new(x as i64, y as i64)] = 3;
+                    }
+                }
+                Some(&Fold::Y(y)) if y < frame.height() => {
+                    for x in 0..frame.width() {
+                        frame[Point::new(x as i64, y as i64)] = 3;
+                    }
+                }
+                Some(_) => {}
+                None => break,
+            }
+            recorder.record(frame)?;
+
+            dot_grid.fold(1);
+            recorder.record(paper(&dot_grid.grid()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Explore for Day13 {
+    type State = DotGrid;
+
+    const COMMANDS: &'static [Command] = &[
+        ("fold", "[n]", "Make the next n folds, 1 by default"),
+        ("count", "", "Count the dots visible"),
+        ("show", "", "Show the paper and the folds left"),
+    ];
+
+    fn start(dot_grid: &DotGrid) -> DotGrid {
+        dot_grid.clone()
+    }
+
+    fn run(dot_grid: &mut DotGrid, name: &str, args: &[&str]) -> Result<String, String> {
+        match name {
+            "fold" => {
+                let folds: usize = arg(args, 0, Some(1), "how many folds to make")?;
+                if dot_grid.instructions.is_empty() {
+                    return Err("every fold was made already".to_owned());
+                }
+
+                let made: Vec<String> = (dot_grid.instructions.iter().take(folds))
+                    .map(Fold::to_string)
+                    .collect();
+                dot_grid.fold(folds);
+                Ok(format!(
+                    "Folded along {}, {} dots visible",
+                    made.join(", "),
+                    dot_grid.count_visible()
+                ))
+            }
+            "count" => Ok(format!("{} dots visible", dot_grid.count_visible())),
+            "show" => {
+                let folds: Vec<String> =
+                    dot_grid.instructions.iter().map(Fold::to_string).collect();
+                Ok(format!(
+                    "{}\nFolds left: {}",
+                    dot_grid.drawing()?.trim_end_matches('\n'),
+                    if folds.is_empty() {
+                        "none".to_owned()
+                    } else {
+                        folds.join(", ")
+                    }
+                ))
+            }
+            _ => Err(format!("command `{}` is not handled", name)),
+        }
+    }
+}
+
+/// Where `coord` ends up after folding along `fold_point`. Coordinates on the fold itself end up
+/// out of the folded paper.
+fn mirror(coord: i64, fold_point: i64) -> i64 {
+    match coord {
+        coord if coord < fold_point => coord,
+        coord if coord == fold_point => -1,
+        coord => fold_point * 2 - coord,
+    }
+}
+
+impl FromStr for DotGrid {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        DotGrid::from_sections(sections(input).map(Ok))
+    }
+}
+
+impl DotGrid {
+    /// Parses a paper from the sections of its input, be they split from a string or read one by
+    /// one
+    fn from_sections<T, E>(
+        mut sections: impl Iterator<Item = Result<Section<T>, E>>,
+    ) -> Result<DotGrid, E>
+    where
+        T: AsRef<str>,
+        E: From<ParseError>,
+    {
+        let coordinates = sections.next().transpose()?;
+        let (raw_coordinates, end) = coordinates.as_ref().map_or(("", 0), |section| {
+            let text = section.text.as_ref();
+            (text, section.line + text.lines().count())
+        });
+        let raw_instructions = sections.next().transpose()?.ok_or_else(|| {
+            ParseError::new(1, "an empty line before the fold instructions").offset_lines(end)
+        })?;
+
+        let (mut width, mut height) = (0, 0);
+        let dots = parse_lines(raw_coordinates, |raw_pair| {
+            let dot = match raw_pair.trim().split_once(',') {
+                Some((x, y)) => Point::new(
+                    parse_number::<u32>(raw_pair, x)?.into(),
+                    parse_number::<u32>(raw_pair, y)?.into(),
+                ),
+                None => {
+                    return Err(ParseError::at(
+                        raw_pair,
+                        raw_pair.trim(),
+                        "a dot like `x,y`",
+                    ))
+                }
+            };
+
+            width = width.max(dot.x as usize + 1);
+            height = height.max(dot.y as usize + 1);
+            Ok(dot)
+        })?;
+
+        // The instructions start after the coordinates and the empty line
+        let instructions = parse_lines(raw_instructions.text.as_ref(), str::parse)
+            .map_err(|err| err.offset_lines(raw_instructions.line))?
+            .into();
+
+        Ok(DotGrid {
+            dots: dots.into_iter().collect(),
+            width,
+            height,
+            instructions,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fold {
+    X(usize),
+    Y(usize),
+}
+
+impl fmt::Display for Fold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fold::X(x) => write!(f, "x={}", x),
+            Fold::Y(y) => write!(f, "y={}", y),
+        }
+    }
+}
+
+impl FromStr for Fold {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let instruction = input.trim();
+        let axis_point = instruction
+            .strip_prefix("fold along ")
+            .ok_or_else(|| ParseError::at(input, instruction, "`fold along`"))?;
+
+        let (axis, point) = axis_point
+            .split_once('=')
+            .ok_or_else(|| ParseError::at(input, axis_point, "an axis like `x=5`"))?;
+
+        let point = parse_number::<usize>(input, point)?;
+
+        match axis {
+            "x" => Ok(Self::X(point)),
+            "y" => Ok(Self::Y(point)),
+            _ => Err(ParseError::at(input, axis, "`x` or `y`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../fixtures/2021/day13/example1.txt");
+
+    #[test]
+    fn it_parses_the_input() {
+        let dot_grid = TEST_INPUT.parse::<DotGrid>().unwrap();
+
+        assert_eq!(dot_grid.instructions, vec![Fold::Y(7), Fold::X(5)]);
+    }
+
+    #[test]
+    fn it_fails_to_parse_bad_instructions() {
+        let err = "6,10\n0,14\n\nfold along y=7\nfold along z=5".parse::<DotGrid>();
+        assert_eq!(
+            err.err(),
+            Some(ParseError::new(12, "`x` or `y`").offset_lines(4))
+        );
+
+        let err = "6,10\n0;14\n\nfold along y=7".parse::<DotGrid>();
+        assert_eq!(
+            err.err(),
+            Some(ParseError::new(1, "a dot like `x,y`").offset_lines(1))
+        );
+
+        let err = "6,10\n0,14\nfold along y=7".parse::<DotGrid>();
+        assert_eq!(
+            err.err(),
+            Some(ParseError::new(1, "an empty line before the fold instructions").offset_lines(3))
+        );
+    }
+
+    #[test]
+    fn it_folds_big_papers() {
+        // A paper as big as these dots would take terabytes, so only the dots are kept
+        let mut dot_grid = "6,10\n1245346,238527\n\nfold along y=7\nfold along x=3"
+            .parse::<DotGrid>()
+            .unwrap();
+        assert_eq!(dot_grid.count_visible(), 2);
+
+        dot_grid.fold(1);
+        assert_eq!(dot_grid.count_visible(), 1);
+        dot_grid.fold(1);
+        assert_eq!(
+            dot_grid.drawing(),
+            Ok("   \n   \n   \n   \n#  \n   \n   \n".to_owned())
+        );
+
+        // Too big to draw, even once folded
+        let dot_grid = "0,0\n4000000,200000\n\nfold along y=100000"
+            .parse::<DotGrid>()
+            .unwrap();
+        let too_big = "4000001×100000 cells are too many to draw, the most is 4194304";
+        assert_eq!(
+            Day13::part2(&dot_grid, &Params::default()),
+            Err(too_big.to_owned())
+        );
+        assert_eq!(
+            Day13::part1(&dot_grid, &Params::default()),
+            Ok(Answer::Number(2))
+        );
+        let mut recorder = Recorder::new();
+        assert_eq!(
+            Day13::record(&dot_grid, &Params::default(), &mut recorder),
+            Err("4000001×200001 cells are too many to draw, the most is 4194304".to_owned())
+        );
+        assert!(recorder.frames().is_empty());
+    }
+
+    #[test]
+    fn it_ignores_folds_past_the_edge() {
+        let mut dot_grid = "0,0\n2,1\n\nfold along x=4000000000\nfold along y=1"
+            .parse::<DotGrid>()
+            .unwrap();
+        dot_grid.fold_all();
+
+        assert_eq!(dot_grid.to_string(), "#  \n");
+    }
+
+    #[test]
+    fn it_counts_visible_points() {
+        let mut dot_grid = TEST_INPUT.parse::<DotGrid>().unwrap();
+        assert_eq!(dot_grid.count_visible(), 18);
+
+        dot_grid.fold(1);
+        assert_eq!(dot_grid.count_visible(), 17);
+    }
+
+    #[test]
+    fn it_records_the_folds() {
+        let dot_grid = TEST_INPUT.parse::<DotGrid>().unwrap();
+        let mut recorder = Recorder::new();
+        Day13::record(&dot_grid, &Params::default(), &mut recorder).unwrap();
+
+        let sizes: Vec<(usize, usize)> = recorder
+            .frames()
+            .iter()
+            .map(|frame| (frame.width(), frame.height()))
+            .collect();
+        assert_eq!(sizes, vec![(11, 15), (11, 15), (11, 7), (11, 7), (5, 7)]);
+        assert!(recorder.frames()[1].row(7).iter().all(|&level| level == 3));
+        assert_eq!(
+            recorder.frames()[4]
+                .cells()
+                .filter(|(_, &level)| level == 2)
+                .count(),
+            16
+        );
+    }
+
+    #[test]
+    fn it_draws_the_folded_paper() {
+        let mut dot_grid = TEST_INPUT.parse::<DotGrid>().unwrap();
+        dot_grid.fold_all();
+
+        assert_eq!(
+            dot_grid.to_string(),
+            "#####\n#   #\n#   #\n#   #\n#####\n     \n     \n"
+        );
+    }
+
+    #[test]
+    fn it_folds_in_the_repl() {
+        let mut dot_grid = Day13::start(&TEST_INPUT.parse().unwrap());
+
+        assert_eq!(
+            Day13::run(&mut dot_grid, "fold", &[]),
+            Ok("Folded along y=7, 17 dots visible".to_owned())
+        );
+        assert!(Day13::run(&mut dot_grid, "show", &[])
+            .unwrap()
+            .ends_with("\nFolds left: x=5"));
+        Day13::run(&mut dot_grid, "fold", &[]).unwrap();
+        assert_eq!(
+            Day13::run(&mut dot_grid, "fold", &[]),
+            Err("every fold was made already".to_owned())
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::io::{self, Write};
+
+use crate::generate::{distinct, join, Rng};
+use crate::year2021::day08::SEGMENTS;
+
+/// Sonar depths, drifting deeper
+pub fn day01(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut depth = rng.between(100, 200);
+    for _ in 0..size {
+        writeln!(out, "{}", depth)?;
+        depth = (depth + rng.between(0, 20)).saturating_sub(8);
+    }
+    Ok(())
+}
+
+/// Submarine commands that never take the depth or the aim above the surface
+pub fn day02(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut aim = 0;
+    for _ in 0..size {
+        let amount = rng.between(1, 9);
+        let direction = match rng.below(20) {
+            0..=7 => "forward",
+            8..=13 if amount <= aim => "up",
+            _ => "down",
+        };
+        match direction {
+            "up" => aim -= amount,
+            "down" => aim += amount,
+            _ => {}
+        }
+        writeln!(out, "{} {}", direction, amount)?;
+    }
+    Ok(())
+}
+
+/// Distinct diagnostic numbers, at least 12 bits wide, so the rating filters end with one
+pub fn day03(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let bits = (12..).find(|bits| 1 << bits >= 2 * size).unwrap();
+    for entry in distinct(size, || rng.below(1 << bits)) {
+        writeln!(out, "{:0width$b}", entry, width = bits)?;
+    }
+    Ok(())
+}
+
+/// Every number from 0 to 99 drawn once, and 5x5 boards of distinct numbers
+pub fn day04(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut numbers: Vec<u32> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    writeln!(out, "{}", join(&numbers, ","))?;
+
+    for _ in 0..size {
+        rng.shuffle(&mut numbers);
+        writeln!(out)?;
+        for row in numbers[..25].chunks(5) {
+            let row: Vec<String> = row.iter().map(|n| format!("{:>2}", n)).collect();
+            writeln!(out, "{}", row.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+/// Horizontal, vertical and diagonal vent lines in a 1000x1000 field
+pub fn day05(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    const FIELD: u64 = 1000;
+
+    for _ in 0..size {
+        let (x1, y1) = (rng.below(FIELD), rng.below(FIELD));
+        let (x2, y2) = match rng.below(3) {
+            0 => (x1, rng.below(FIELD)),
+            1 => (rng.below(FIELD), y1),
+            _ => {
+                let (right, down) = (rng.one_in(2), rng.one_in(2));
+                let room_x = if right { FIELD - 1 - x1 } else { x1 };
+                let room_y = if down { FIELD - 1 - y1 } else { y1 };
+                let len = rng.below(room_x.min(room_y) + 1);
+                let x2 = if right { x1 + len } else { x1 - len };
+                let y2 = if down { y1 + len } else { y1 - len };
+                (x2, y2)
+            }
+        };
+        writeln!(out, "{},{} -> {},{}", x1, y1, x2, y2)?;
+    }
+    Ok(())
+}
+
+/// Lanternfish timers, like the puzzle's from 1 to 5
+pub fn day06(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let timers: Vec<u64> = (0..size).map(|_| rng.between(1, 5)).collect();
+    writeln!(out, "{}", join(&timers, ","))
+}
+
+/// Crab positions, mostly small like in the puzzle
+pub fn day07(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let positions: Vec<u64> = (0..size)
+        .map(|_| {
+            let max = rng.below(2000);
+            rng.below(max + 1)
+        })
+        .collect();
+    writeln!(out, "{}", join(&positions, ","))
+}
+
+/// Entries with the 10 patterns of a randomly wired display, and 4 digits shown on it
+pub fn day08(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let mut wires: Vec<char> = "abcdefg".chars().collect();
+        rng.shuffle(&mut wires);
+
+        let pattern = |rng: &mut Rng, digit: usize| {
+            let mut segments: Vec<char> = SEGMENTS[digit]
+                .bytes()
+                .map(|segment| wires[(segment - b'a') as usize])
+                .collect();
+            rng.shuffle(&mut segments);
+            segments.into_iter().collect::<String>()
+        };
+
+        let mut digits: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut digits);
+        let patterns: Vec<String> = digits.iter().map(|&d| pattern(rng, d)).collect();
+        let output: Vec<String> = (0..4)
+            .map(|_| {
+                let digit = rng.below(10) as usize;
+                pattern(rng, digit)
+            })
+            .collect();
+
+        writeln!(out, "{} | {}", patterns.join(" "), output.join(" "))?;
+    }
+    Ok(())
+}
+
+/// A square height map of basins walled by 9s. Each basin gets deeper towards a random centre,
+/// one per 8x8 block.
+pub fn day09(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    const BLOCK: usize = 8;
+
+    let blocks = size.div_ceil(BLOCK);
+    let centres: Vec<(usize, usize)> = (0..blocks * blocks)
+        .map(|i| {
+            let (bx, by) = (i % blocks, i / blocks);
+            let x = bx * BLOCK + rng.below(BLOCK as u64) as usize;
+            let y = by * BLOCK + rng.below(BLOCK as u64) as usize;
+            (x, y)
+        })
+        .collect();
+
+    // The closest centre to a point is always in its block or one next to it
+    let closest = |x: usize, y: usize| {
+        let (bx, by) = (x / BLOCK, y / BLOCK);
+        let near = |b: usize| b.saturating_sub(1)..(b + 2).min(blocks);
+        near(by)
+            .flat_map(|ny| near(bx).map(move |nx| ny * blocks + nx))
+            .map(|i| (centres[i].0.abs_diff(x) + centres[i].1.abs_diff(y), i))
+            .min()
+            .unwrap()
+    };
+
+    for y in 0..size {
+        let row: String = (0..size)
+            .map(|x| {
+                let (distance, basin) = closest(x, y);
+                let wall = (x + 1 < size && closest(x + 1, y).1 != basin)
+                    || (y + 1 < size && closest(x, y + 1).1 != basin);
+                if wall {
+                    '9'
+                } else {
+                    char::from(b'0' + distance.min(8) as u8)
+                }
+            })
+            .collect();
+        writeln!(out, "{}", row)?;
+    }
+    Ok(())
+}
+
+const OPENING: [char; 4] = ['(', '[', '{', '<'];
+const CLOSING: [char; 4] = [')', ']', '}', '>'];
+
+/// Navigation lines that are either corrupted or incomplete. There is always an odd amount of
+/// incomplete ones, so they have a middle score.
+pub fn day10(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut incomplete: Vec<bool> = (0..size).map(|_| rng.one_in(2)).collect();
+    if incomplete.iter().filter(|&&line| line).count() % 2 == 0 {
+        if let Some(last) = incomplete.last_mut() {
+            *last = !*last;
+        }
+    }
+
+    for incomplete in incomplete {
+        let len = rng.between(20, 110) as usize;
+        let corrupt_at = if incomplete {
+            None
+        } else {
+            Some(rng.below(len as u64) as usize)
+        };
+
+        let mut line = String::new();
+        let mut open: Vec<usize> = vec![];
+        let mut corrupted = false;
+
+        for i in 0..len {
+            // Unclosed chunks are capped so the completion scores fit in 64 bits
+            if open.is_empty() || (open.len() < 20 && rng.between(1, 20) <= 11) {
+                let kind = rng.below(4) as usize;
+                open.push(kind);
+                line.push(OPENING[kind]);
+            } else if corrupt_at.is_some_and(|at| i >= at) {
+                corrupted = true;
+                break;
+            } else {
+                line.push(CLOSING[open.pop().unwrap()]);
+            }
+        }
+
+        if incomplete && open.is_empty() {
+            line.push(OPENING[rng.below(4) as usize]);
+        } else if !incomplete {
+            if !corrupted && open.is_empty() {
+                open.push(rng.below(4) as usize);
+                line.push(OPENING[*open.last().unwrap()]);
+            }
+            let wrong = (open.last().unwrap() + rng.between(1, 3) as usize) % 4;
+            line.push(CLOSING[wrong]);
+        }
+
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// A square grid of random octopus energy levels. Most never flash all at once, so part 2 of them
+/// ends with an error.
+pub fn day11(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let row: String = (0..size)
+            .map(|_| char::from(b'0' + rng.below(10) as u8))
+            .collect();
+        writeln!(out, "{}", row)?;
+    }
+    Ok(())
+}
+
+/// Distinct dots on a transparent paper, and the folds that bring it down to 40x6. The paper is
+/// folded in half every time, alternating between `x` and `y`, and no dot sits on a fold line.
+pub fn day13(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let (mut width, mut height) = (40, 6);
+    let mut folds = vec![];
+    while folds.len() < 2 || width * height < 2 * size as u64 {
+        if folds.len() % 2 == 0 {
+            folds.push(('x', width));
+            width = width * 2 + 1;
+        } else {
+            folds.push(('y', height));
+            height = height * 2 + 1;
+        }
+    }
+
+    // Dots start on the folded paper and are mirrored to either side of every fold
+    let dots = distinct(size, || {
+        let (mut x, mut y) = (rng.below(40), rng.below(6));
+        for &(axis, line) in &folds {
+            if rng.one_in(2) {
+                match axis {
+                    'x' => x = line * 2 - x,
+                    _ => y = line * 2 - y,
+                }
+            }
+        }
+        (x, y)
+    });
+
+    for (x, y) in dots {
+        writeln!(out, "{},{}", x, y)?;
+    }
+    writeln!(out)?;
+    for (axis, line) in folds.iter().rev() {
+        writeln!(out, "fold along {}={}", axis, line)?;
+    }
+    Ok(())
+}
+
+/// Lists of numbers to check a day whose input is one comma-separated line against a simpler
+/// solution. Half are made by `hand_made`, to reach the edge cases, and the other half are
+/// generated inputs of up to `max_size` numbers, like the puzzle's.
+#[cfg(test)]
+pub(crate) fn number_lists(
+    day: u8,
+    max_size: u64,
+    mut hand_made: impl FnMut(&mut Rng) -> Vec<u64>,
+) -> impl Iterator<Item = Vec<u64>> {
+    let mut rng = Rng::new(day.into());
+
+    (0..300).map(move |seed| {
+        if seed % 2 == 0 {
+            hand_made(&mut rng)
+        } else {
+            let size = rng.between(1, max_size) as usize;
+            let input = crate::generate::generate(2021, day, size, seed).unwrap();
+            (input.trim_end().split(','))
+                .map(|n| n.parse().unwrap())
+                .collect()
+        }
+    })
+}